@@ -0,0 +1,136 @@
+use battleship::board::GameData;
+use battleship::enums::{FileFormat, StateCreate};
+use crate::cli::handler;
+use battleship::utils;
+use crate::cli::output::output_string;
+
+// Refactored command line function, false if a verify, replay or query batch failed so main can exit non-zero
+pub fn command_line_input(myboard: &mut GameData) -> bool{
+    let args: Vec<String> = std::env::args().collect();
+    let mut args_iter = args.iter().skip(1).peekable();     // Skip program name, peek so lists stop at the next command
+    let mut mystate = Vec::<StateCreate>::new();              // Keep track of the state of Create commands
+    let mut query_file: Option<String> = None;                  // Queries are answered once every argument is read
+    let mut out_file: Option<String> = None;
+    let mut arena_names: Option<String> = None;                 // Arena runs last as well, after any board settings
+    let mut arena_games: usize = 100;
+    let mut passed = true;
+    let mut file_format: Option<FileFormat> = None;             // Format for the file commands after it
+
+    while let Some(arg) = args_iter.next() {
+        match arg.to_uppercase().as_str() {
+            "--LOAD" => {
+                handler::handle_load(myboard, &mut args_iter, file_format);
+            },
+            "--VERIFY" => {
+                passed &= handler::handle_verify(myboard, &mut args_iter, file_format);
+            },
+            "--LENIENT" => {
+                myboard.set_lenient(true);
+            },
+            "--FORMAT" => {
+                file_format = handler::handle_format(&mut args_iter);
+            },
+            "--CONVERT" => {
+                handler::handle_convert(myboard, &mut args_iter, file_format);
+            },
+            "--HELP" => {
+                handler::handle_help();
+            }
+            "--EXIT" | "--QUIT" => {
+                output_string("Thank you for playing!");
+                break;
+            },
+            "--GUESS" => {
+                handler::handle_guess(myboard, &mut args_iter);
+            },
+            "--QUERIES" => {
+                query_file = handler::handle_filename_arg(&mut args_iter, "--queries");
+            },
+            "--OUT" => {
+                out_file = handler::handle_filename_arg(&mut args_iter, "--out");
+            },
+            "--SEED" => {
+                handler::handle_seed(myboard, &mut args_iter);
+            },
+            "--AI" => {
+                handler::handle_ai_level(myboard, &mut args_iter);
+            },
+            "--SAVE" => {
+                handler::handle_save(myboard, &mut args_iter);
+            },
+            "--RESUME" => {
+                handler::handle_resume(myboard, &mut args_iter);
+            },
+            "--TRANSCRIPT" => {
+                handler::handle_transcript(myboard, &mut args_iter);
+            },
+            "--REPLAY" => {
+                passed &= handler::handle_replay(&mut args_iter);
+            },
+            "--AISHOT" => {
+                handler::handle_ai_shot(myboard, &mut args_iter);
+            },
+            "--ARENA" => {
+                match args_iter.next() {
+                    Some(names) => arena_names = Some(names.clone()),
+                    None => output_string("Usage: --arena <level,level,...> [--games <count>]"),
+                }
+            },
+            "--GAMES" => {
+                match args_iter.next().map(|next_arg| utils::parse_to_usize(next_arg)) {
+                    Some(Ok(games)) => arena_games = games,
+                    _ => output_string("Error: Games must be a number greater than 0"),
+                }
+            },
+            "--CREATE" => {
+                if handler::handle_create(myboard, &mut args_iter, &mut mystate) {      // Successful file create
+                    continue;
+                } else {
+                    break;
+                }
+            },
+            "--ROW" => {
+                if let Err(err) = handler::handle_row_col(myboard, &mut args_iter, true) {
+                    handler::handle_row_col_error(err, true);
+                }
+            },
+            "--COL" => {
+                if let Err(err) = handler::handle_row_col(myboard, &mut args_iter, false) {
+                    handler::handle_row_col_error(err, false);
+                }
+            },
+            "--SHIPS" => {
+                handler::handle_ships_size(myboard, &mut args_iter, &mut mystate);
+            },
+            "--PLAYER" => {
+                handler::handle_player(myboard, &mut args_iter, &mut mystate);
+            },
+            "--RANDOM" => {
+                handler::handle_random(myboard, &mystate);
+            },
+            "--DISPLAY" => {
+                handler::handle_display(myboard, &mut args_iter, file_format);
+            },
+            "--PLACE" => {
+                handler::handle_place_ship(myboard, &mut args_iter, &mystate);
+            },
+            &_ => {
+                output_string("Error: Unrecognized command.");
+                break;
+            }
+        }
+    };
+    if let Some(queries) = query_file {
+        passed &= handler::handle_queries(myboard, &queries, out_file.as_deref());
+    }
+    if let Some(names) = arena_names {
+        handler::handle_arena(myboard, &names, arena_games);
+    }
+    if mystate.contains(&StateCreate::StateCreate) {                 // We need to write the file at the end
+        if let Some(format) = file_format {
+            myboard.set_format(format);
+        }
+        handler::handle_write_file(myboard);
+    }
+    passed
+}
@@ -0,0 +1,871 @@
+use battleship::enums::StateCreate;
+use battleship::board::GameData;
+use crate::cli::output::{handle_file_error, output_string};
+use battleship::enums::Direction;
+use battleship::board::PlayBoard;
+use battleship::file;
+use battleship::board::ShipBoundingBox;
+use battleship::coord::Coord;
+use battleship::utils;
+use battleship::enums;
+use battleship::enums::FileFormat;
+use battleship::error::BattleshipError;
+use battleship::board;
+use battleship::datacheck;
+use battleship::json;
+use battleship::save;
+use battleship::session::TurnReport;
+use battleship::transcript;
+use crate::cli::interactive;
+use battleship::verify;
+use battleship::aistuff::AiBoard;
+use battleship::arena;
+use battleship::strategy::{ObservationBoard, RandomPlacer, Strategy};
+
+// Function to handle loading files, false if nothing was loaded
+pub fn handle_load(
+    myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    format: Option<FileFormat>) -> bool
+{
+    let filename = match args_iter.next() {
+        Some(filename) => filename,
+        None => {
+            output_string("Usage: --load <filename>");
+            return false;
+        }
+    };
+    reset_board(myboard);
+    match file::load_file_format(filename, myboard, format) {
+        Ok(_) => {
+            print_warnings(myboard);
+            output_string("File loaded successfully.");
+            true
+        },
+        Err(err) => {
+            output_string(&format!("Error: Failed to load file: {}", err));
+            false
+        }
+    }
+}
+
+// Check every line of a board file and print each problem found.  A file that passes is loaded
+pub fn handle_verify(
+    myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    format: Option<FileFormat>) -> bool
+{
+    let filename = match args_iter.next() {
+        Some(filename) => filename,
+        None => {
+            output_string("Usage: --verify <filename>");
+            return false;
+        }
+    };
+    reset_board(myboard);
+    let report = match verify::verify_file(filename, myboard, format) {
+        Ok(report) => report,
+        Err(err) => {
+            output_string(&format!("Failed: {}", err));
+            return false;
+        }
+    };
+    for violation in report.violations.iter() {
+        output_string(&violation.to_string());
+    }
+    if !report.is_valid() {
+        output_string(&format!("Failed: {} errors, {} warnings in {}", report.errors(), report.warnings(), filename));
+        return false;
+    }
+    if let Err(err) = file::load_file_format(filename, myboard, format) {       // Verify and the loader should agree, but say so if not
+        output_string(&format!("Failed: {}", err));
+        return false;
+    }
+    output_string(&format!("Passed: File loaded successfully. {} players, {} warnings.", report.players, report.warnings()));
+    true
+}
+
+// If there is already a board loaded reinitialize, keeping a given seed and the lenient setting
+pub fn reset_board(myboard: &mut GameData) {
+    if myboard.get_loaded() {
+        let seed = myboard.get_seeded().then(|| myboard.get_seed());
+        let lenient = myboard.get_lenient();
+        *myboard = GameData::default();
+        if let Some(seed) = seed {
+            myboard.set_seed(seed);
+        }
+        myboard.set_lenient(lenient);
+    }
+}
+
+// Problems a lenient load let through
+pub fn print_warnings(myboard: &GameData) {
+    for warning in myboard.get_warnings() {
+        output_string(&format!("Warning: {}", warning));
+    }
+}
+
+pub fn handle_row_col_error(err: BattleshipError, is_col: bool) {
+    let err_msg = match err {
+        BattleshipError::TooSmall => if is_col { "Error: Column value must be greater than or equal to 1" } else { "Error: Row value must be greater than or equal to 1" },
+        BattleshipError::TooBig => {
+            let max_size = enums::MAX_SIZE;  // Avoid referencing temporary format string
+            return output_string(&format!("Error: {} value must be less than {}", if is_col { "Column" } else { "Row" }, max_size));
+        }
+        _ => if is_col { "Error: Column is not a valid value" } else { "Error: Row is not a valid value" },
+    };
+    output_string(err_msg);
+}
+
+// Handle conversion and storage of row and column data in Create specifically but later probably in load.
+pub fn handle_row_col(
+    myboard: &mut GameData, 
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>, 
+    is_col: bool 
+) -> Result<(), BattleshipError> {
+    if let Some(next_value) = args_iter.next() {
+        let value = utils::parse_to_usize(next_value)?;
+        myboard.set_row_or_col(value, is_col);
+        Ok(())
+    } else {
+        Err(BattleshipError::NotANumber(String::new()))
+    }
+}
+
+
+// Handle function to start the file creation
+pub fn handle_create(myboard: &mut GameData, args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+                mystate: &mut Vec<StateCreate>) -> bool {
+     // Function call for Create with path
+     let seed = myboard.get_seeded().then(|| myboard.get_seed());
+     *myboard = GameData::default();            // Create a new board to start population
+     if let Some(seed) = seed {
+         myboard.set_seed(seed);                // An earlier --seed still applies
+     }
+                
+     if let Some(next_guess) = args_iter.next() {
+         myboard.set_filename(next_guess.to_string());
+         mystate.push(StateCreate::StateFileName);   // Keep track that a filename was added
+         mystate.push(StateCreate::StateCreate);     // Keep track that we are in create
+     }
+     else {
+         output_string("Error: Missing path for Create command");
+         return false;
+     }
+     true
+}
+
+// Handle function to set ship min/max size
+pub fn handle_ships_size(
+    myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    mystate: &mut Vec<StateCreate>
+    ) -> bool {
+
+    if !mystate.contains(&StateCreate::StateCreate) {
+        output_string("Error: Called Ships without a valid create and file path");
+        return false;
+    }
+    if let Some(next_guess) = args_iter.next() {
+        match utils::parse_to_usize(next_guess) {
+            Ok(n) => {
+                let sizes = if mystate.contains(&StateCreate::StateShips) {
+                    // Ships has been called before
+                    let (_small, large) = myboard.get_shipsizes(); // large is already usize
+                    let new_large = Some(large.max(n)); 
+                    
+                    myboard.set_shipsizes(n, new_large)
+                }
+                else {
+                    mystate.push(StateCreate::StateShips);
+                    myboard.set_shipsizes(n, None)
+                };
+                if let Err(err) = sizes {
+                    output_string(&err.to_string());
+                    return false;
+                }
+            },
+            Err(err) => {
+                match err {
+                    BattleshipError::TooSmall => output_string("Error: A ship must be at least 1 or greater"),
+                    BattleshipError::TooBig => output_string(&format!("Error: A ship must be smaller than {}", enums::MAX_SIZE)),
+                    _ => output_string("Error: Unable to convert Ships value to integer"),
+                }
+                return false;
+            }
+        }
+    }    
+    true
+}
+
+// This is the first function call that requires everything else to be set
+// Sets default rows/cols if not previously set
+
+pub fn handle_player(
+    myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    mystate: &mut Vec<StateCreate>) -> bool {
+        
+        
+    if !mystate.contains(&StateCreate::StateCreate) {
+        output_string("Error: Called Player without a valid create or file path");
+        return false;
+    }
+    if let Some(next_guess) = args_iter.next() {            // A string to name a player
+        if next_guess.starts_with("--") {
+            output_string(&format!("Error: Found command {} instead of a player name.", next_guess));
+            return false;
+        }
+        let mut tmp_player = PlayBoard::default();
+        tmp_player.set_playername(next_guess.to_string());
+        tmp_player.set_playernum(myboard.get_boards_len() + 1);
+        mystate.push(StateCreate::StatePlayer);
+        myboard.increment_playercount();
+        myboard.boards_add(tmp_player);  // Add the player and update player count
+    }
+    else {
+        output_string("Error: Empty playername found!");
+        return false;
+    }
+    true
+}
+
+pub fn handle_help() {
+    output_string(
+        "Available commands: \
+         --load <filename>\n--verify <filename> (report every problem, exit code 1 on failure)\n--lenient (before --load or --verify, warn when the player count is off)\n--format <legacy, v2 or json> (before --load, --verify, --display, --convert or --create)\n--convert <infile> <outfile> (between legacy and v2, or to --format)\n--guess [<shooter>x]<player>:<list in A1 or AA10 format> (name the shooter to play by the rules, needed while recording)\n--queries <file or - for stdin> [--out <file>]\n--ai <0-4 or random, hunt, parity, probability, montecarlo>\n--aishot <player>\n--arena <level,level,...> [--games <count>]\n--seed <number>\n--save <filename> (the whole game, including turn, random numbers and AI)\n--resume <filename> (carry on a game written by --save)\n--transcript <filename> (after --load, record every shot)\n--replay <transcript> (step through a recorded game with next, prev and goto N)\n--help (this output)\n--exit or --quit to quit.",
+    );
+}
+
+
+pub fn handle_random(myboard: &mut GameData, mystate: &[StateCreate]) -> bool {
+    if !mystate.contains(&StateCreate::StatePlayer) {
+        output_string("Error: No player currently under creation.");
+        return false;
+    }
+
+    if let Some(mut myplayer) = myboard.boards_pop_last() {
+        let mut placer = RandomPlacer;
+        let mut rng = myboard.take_rng();
+        let placed = placer.place_fleet(myboard, &mut myplayer, &mut rng);
+        myboard.set_rng(rng);
+        output_string(&format!("Placing fleet for {} with seed {}", myplayer.get_playername(), myboard.get_seed()));
+        if let Err(err) = &placed {
+            output_string(&err.to_string());
+        }
+        for ship in myplayer.return_ships() {
+            output_string(&format!("Added ship {} at {}", ship.ship_id, ship.start));
+        }
+
+        // Add the updated player back to the board
+        myboard.boards_add(myplayer);
+        return placed.is_ok();
+    }
+    false
+}
+
+// Fire shots at the loaded boards.  Shot lists are <player>:<shots> e.g. 2:A1,B7,AA10, player 1 if omitted
+pub fn handle_guess(myboard: &mut GameData, 
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    if !myboard.get_loaded() {
+        output_string("Error: You have not loaded a file yet!");
+        while args_iter.next_if(|next_arg| !next_arg.starts_with("--")).is_some() {}  // Skip the shots, not the next command
+        return false;
+    }
+    let mut found_shots = false;
+    while let Some(next_guess) = args_iter.next_if(|next_arg| !next_arg.starts_with("--")) {
+        found_shots = true;
+        let (player_str, shot_list) = match next_guess.split_once(':') {
+            Some((player, shots)) => (player, shots),
+            None => ("1", next_guess.as_str()),
+        };
+        let (shooter_str, target_str) = match player_str.split_once(['x', 'X']) {   // 1x2 names the shooter
+            Some((shooter, target)) => (Some(shooter), target),
+            None => (None, player_str),
+        };
+        let mut players = Vec::new();
+        for num_str in shooter_str.into_iter().chain([target_str]) {
+            match utils::parse_to_usize(num_str.trim()) {
+                Ok(n) if n <= myboard.get_boards_len() => players.push(n),
+                _ => {
+                    output_string(&format!("Error: Invalid playerid, {}", num_str));
+                    return false;
+                }
+            }
+        }
+        let playernum = players[players.len() - 1];
+        let shooter = shooter_str.map(|_| players[0]);
+        if shooter.is_none() && myboard.get_transcript().is_some() {
+            output_string(&format!("Error: Name the shooter, e.g. 1x{}:{}, while a transcript is recording.", playernum, shot_list));
+            continue;
+        }
+        for shot in shot_list.split(',').filter(|x| !x.trim().is_empty()) {
+            match guess_shot(myboard, shooter, playernum, shot) {
+                Ok(result) => output_string(&format!("Player {} {}: {}", playernum, shot.trim().to_uppercase(), result)),
+                Err(err) => {
+                    match err {
+                        BattleshipError::InvalidFormat(_) => output_string(&format!("Error: Invalid coordinate format, {}", shot)),
+                        BattleshipError::InvalidRow(_) => output_string(&format!("Error: Invalid row, {}", shot)),
+                        BattleshipError::InvalidColumn(_) => output_string(&format!("Error: Invalid column, {}", shot)),
+                        BattleshipError::OutOfBounds(_) => output_string(&format!("Player {} {}: OUT-OF-BOUNDS", playernum, shot.trim().to_uppercase())),
+                        BattleshipError::InvalidPlayer(_) => output_string(&format!("Error: Invalid playerid, {}", playernum)),
+                        _ => output_string(&err.to_string()),
+                    }
+                }
+            }
+        }
+    }
+    if !found_shots {
+        output_string("Usage: --guess [<shooter>x]<player>:<list in A1 or AA10 format>");
+        return false;
+    }
+    true
+}
+
+// Set the seed for every random decision that follows
+pub fn handle_seed(myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    match args_iter.next().map(|next_arg| next_arg.trim().parse::<u64>()) {
+        Some(Ok(seed)) => {
+            myboard.set_seed(seed);
+            output_string(&format!("Seed set to {}.", seed));
+            true
+        }
+        _ => {
+            output_string("Usage: --seed <0 to 18446744073709551615>");
+            false
+        }
+    }
+}
+
+// Set the AI difficulty from a level number or name
+pub fn handle_ai_level(myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    match args_iter.next() {
+        Some(next_arg) => set_ai_level(myboard, next_arg),
+        None => {
+            output_string("Usage: --ai <0-4 or random, hunt, parity, probability, montecarlo>");
+            false
+        }
+    }
+}
+
+pub fn set_ai_level(myboard: &mut GameData, level: &str) -> bool {
+    match enums::Intelligence::parse(level) {
+        Some(level) => {
+            myboard.set_ai_level(level);
+            output_string(&format!("AI level set to {} ({}).", level.get_level(), level.get_name()));
+            true
+        }
+        None => {
+            output_string(&format!("Error: Unknown AI level {}.", level));
+            false
+        }
+    }
+}
+
+// Let the AI fire one shot at a player (1 based) using the current AI level
+pub fn handle_ai_shot(myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    match args_iter.next() {
+        Some(next_arg) if !next_arg.starts_with("--") => ai_shot(myboard, next_arg),
+        _ => {
+            output_string("Usage: --aishot <player>");
+            false
+        }
+    }
+}
+
+pub fn ai_shot(myboard: &mut GameData, player_str: &str) -> bool {
+    if !myboard.get_loaded() {
+        output_string("Error: You have not loaded a file yet!");
+        return false;
+    }
+    let playernum = match utils::parse_to_usize(player_str.trim()) {
+        Ok(n) if n <= myboard.get_boards_len() => n,
+        _ => {
+            output_string(&format!("Error: Invalid playerid, {}", player_str));
+            return false;
+        }
+    };
+    let mut ai = AiBoard::new(PlayBoard::default(), myboard.get_ai_level().get_level());
+    let observed = match myboard.boards_get_player(playernum - 1) {
+        Some(target) => ObservationBoard::from_player(myboard, target),
+        None => return false,
+    };
+    let shot = ai.choose_shot(&observed, myboard.get_rng());
+    myboard.set_ai_heat_map(ai.get_heat_map().clone());
+    match shot {
+        Some(coord) => {
+            match fire_shot(myboard, playernum, &coord.to_string()) {
+                Ok(result) => output_string(&format!("AI ({}, seed {}) Player {} {}: {}", myboard.get_ai_level().get_name(), myboard.get_seed(), playernum, coord, result)),
+                Err(err @ (BattleshipError::OwnBoard | BattleshipError::InvalidTarget(_) | BattleshipError::GameOver)) => {
+                    output_string(&err.to_string());
+                    return false;
+                }
+                Err(_) => output_string(&format!("Error: AI chose an invalid shot {}", coord)),
+            }
+            true
+        }
+        None => {
+            output_string(&format!("Error: No shots left on player {}", playernum));
+            false
+        }
+    }
+}
+
+// Pit AI levels against each other, names are comma separated e.g. hunt,probability
+pub fn handle_arena(myboard: &GameData, names: &str, games: usize) -> bool {
+    let mut strategies: Vec<Box<dyn Strategy>> = Vec::new();
+    for name in names.split(',').filter(|x| !x.trim().is_empty()) {
+        match enums::Intelligence::parse(name) {
+            Some(level) => strategies.push(Box::new(AiBoard::new(PlayBoard::default(), level.get_level()))),
+            None => {
+                output_string(&format!("Error: Unknown AI level {}.", name));
+                return false;
+            }
+        }
+    }
+    let report = match arena::run_arena(myboard, &mut strategies, games) {
+        Ok(report) => report,
+        Err(err) => {
+            output_string(&err.to_string());
+            return false;
+        }
+    };
+    let (cols, rows) = myboard.get_col_row();
+    let (small, large) = myboard.get_shipsizes();
+    output_string(&format!("Arena: {} games on {}x{} with ships {}..={}, seed {}, {} unfinished",
+        report.games, cols, rows, small, large, report.seed, report.unfinished));
+    for (num, stats) in report.stats.iter().enumerate() {
+        let (low, high) = stats.win_rate_interval();
+        let mut line = format!("{} {}: wins {} ({:.1}% [95% CI {:.1}%-{:.1}%])",
+            num + 1, stats.name, stats.wins, stats.win_rate() * 100.0, low * 100.0, high * 100.0);
+        if let (Some(mean), Some((mean_low, mean_high))) = (stats.mean_shots(), stats.mean_shots_interval()) {
+            line.push_str(&format!(", shots to win mean {:.1} [95% CI {:.1}-{:.1}] median {} p90 {} p95 {}",
+                mean, mean_low, mean_high,
+                stats.percentile_shots(50).unwrap_or(0),
+                stats.percentile_shots(90).unwrap_or(0),
+                stats.percentile_shots(95).unwrap_or(0)));
+        }
+        output_string(&line);
+    }
+    true
+}
+
+// Check a single shot against a player (1 based) before it is fired
+fn parse_shot(myboard: &GameData, playernum: usize, shot: &str) -> Result<Coord, BattleshipError> {
+    let coord: Coord = shot.parse()?;
+    if !myboard.in_bounds(coord) {
+        return Err(BattleshipError::OutOfBounds(shot.to_string()));
+    }
+    if playernum > myboard.get_boards_len() {
+        return Err(BattleshipError::InvalidPlayer(playernum.to_string()));
+    }
+    Ok(coord)
+}
+
+// The current player fires a single shot at a player (1 based) and the turn passes on
+fn fire_shot(myboard: &mut GameData, playernum: usize, shot: &str) -> Result<String, BattleshipError> {
+    let coord = parse_shot(myboard, playernum, shot)?;
+    let report = match myboard.fire(playernum - 1, coord) {     // Same rules as GameSession
+        Ok(report) => report,
+        Err(BattleshipError::AlreadyGuessed(_)) => return Ok("ALREADY-GUESSED".to_string()),
+        Err(err) => return Err(err),
+    };
+    record_move(myboard, &report);
+    Ok(shot_text(report.result, report.eliminated))
+}
+
+// A scripted --guess shot at a player (1 based), outside the turn order so a shot list can be fired in one go.
+// Own board and eliminated targets are refused when the shooter (1 based) is named
+fn guess_shot(myboard: &mut GameData, shooter: Option<usize>, playernum: usize, shot: &str) -> Result<String, BattleshipError> {
+    let coord = parse_shot(myboard, playernum, shot)?;
+    let (result, eliminated) = match myboard.fire_out_of_turn(shooter.map(|num| num - 1), playernum - 1, coord) {
+        Ok(outcome) => outcome,
+        Err(BattleshipError::AlreadyGuessed(_)) => return Ok("ALREADY-GUESSED".to_string()),
+        Err(err) => return Err(err),
+    };
+    if let Some(shooter) = shooter {
+        let report = TurnReport { turn: myboard.get_turn(), shooter: shooter - 1, target: playernum - 1, coord, result, eliminated };
+        record_move(myboard, &report);
+    }
+    Ok(shot_text(result, eliminated))
+}
+
+// Append a shot to the transcript if one is recording
+fn record_move(myboard: &GameData, report: &TurnReport) {
+    if let Some(filename) = myboard.get_transcript() {
+        if let Err(err) = transcript::append_move(&filename, report) {
+            output_string(&format!("Warning: Move not added to the transcript: {}", err));
+        }
+    }
+}
+
+fn shot_text(result: enums::ShotResult, eliminated: bool) -> String {
+    match result {
+        enums::ShotResult::Sunk(ship_id) if eliminated => format!("SUNK {} ALL-SUNK", ship_id),
+        enums::ShotResult::Sunk(ship_id) => format!("SUNK {}", ship_id),
+        enums::ShotResult::Hit(_) => "HIT".to_string(),
+        enums::ShotResult::Miss => "MISS".to_string(),
+    }
+}
+
+pub fn handle_display(myboard: &GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    format: Option<FileFormat>) -> bool{
+    let mut named_board = GameData::default();
+    let shown: &GameData = if let Some(next_guess) = args_iter.next() {
+        if next_guess.starts_with("--") {
+            output_string("Error: expected filename and found command.");
+            return false;
+        }
+        if let Err(err) = file::load_file_format(next_guess, &mut named_board, format) {   // Read as --format says, like --load
+            output_string(&format!("Error: Failed to load file: {}", err));
+            return false;
+        }
+        &named_board                                    // Use the file given
+    }
+    else {
+        myboard                                         // Use the boards in memory
+    };
+    if format == Some(FileFormat::Json) {
+        return match json::game_to_json(shown) {
+            Ok(text) => {
+                println!("{}", text);
+                true
+            }
+            Err(err) => {
+                output_string(&err.to_string());
+                false
+            }
+        };
+    }
+    display_boards(shown, false)
+}
+
+// Print every player's board, with the guesses taken at it when shots is set
+pub fn display_boards(shown: &GameData, shots: bool) -> bool {
+    for player in shown.get_boards() {
+        if let Some(largest) = player.get_largest_ship_id() {
+            let (_cols, rows) = shown.get_col_row();
+            let count = largest.max(rows).to_string().len() + 1;    // Widest label plus a space between cells
+            output_string(player.get_playername());
+            if shots {
+                print!("{}", utils::render_board(shown, &board::create_shot_board_from_player(shown, player), count));
+            } else {
+                print!("{}", utils::render_board(shown, &board::create_my_board_from_player(shown, player), count));
+            }
+        } else {
+            output_string("Error: No ships enrolled!");
+            return false;
+        }
+    }
+    true
+}
+
+// Read the format named after --format
+pub fn handle_format(args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> Option<FileFormat> {
+    match args_iter.next().map(|next_arg| FileFormat::parse(next_arg)) {
+        Some(Some(format)) => Some(format),
+        _ => {
+            output_string("Usage: --format <legacy, v2 or json>");
+            None
+        }
+    }
+}
+
+// Load a board file in either format and write it to another file, in format if given or else the other format
+pub fn handle_convert(myboard: &GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    format: Option<FileFormat>) -> bool {
+    let (infile, outfile) = match (args_iter.next(), args_iter.next()) {
+        (Some(infile), Some(outfile)) if !infile.starts_with("--") && !outfile.starts_with("--") => (infile, outfile),
+        _ => {
+            output_string("Usage: --convert <infile> <outfile>");
+            return false;
+        }
+    };
+    let mut converted = GameData::default();
+    if myboard.get_seeded() {
+        converted.set_seed(myboard.get_seed());             // Recorded in v2 and JSON, a source with a seed keeps its own
+    }
+    converted.set_lenient(myboard.get_lenient());
+    if let Err(err) = file::load_file(infile, &mut converted) {
+        output_string(&format!("Error: Failed to load file: {}", err));
+        return false;
+    }
+    print_warnings(&converted);
+    let from = converted.get_format();
+    let to = format.unwrap_or(if from == FileFormat::Legacy { FileFormat::V2 } else { FileFormat::Legacy });
+    converted.set_format(to);
+    converted.set_filename(outfile.to_string());
+    match file::write_file(&converted) {
+        Ok(()) => {
+            output_string(&format!("Converted {} ({}) to {} ({}).", infile, from.get_name(), outfile, to.get_name()));
+            true
+        }
+        Err(err) => {
+            output_string(&err.to_string());
+            false
+        }
+    }
+}
+
+// Take the filename following a command, refusing another command in its place
+pub fn handle_filename_arg(args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>, command: &str) -> Option<String> {
+    match args_iter.next() {
+        Some(next_arg) if next_arg.starts_with("--") && next_arg != "-" => {
+            output_string(&format!("Error: expected filename for {} and found command {}.", command, next_arg));
+            None
+        }
+        Some(next_arg) => Some(next_arg.clone()),
+        None => {
+            output_string(&format!("Usage: {} <filename>", command));
+            None
+        }
+    }
+}
+
+// Answer a file of queries (- for stdin) against the loaded boards, writing to outfile or stdout
+pub fn handle_queries(myboard: &GameData, queryfile: &str, outfile: Option<&str>) -> bool {
+    if !myboard.get_loaded() {
+        output_string("Error: You have not loaded a file yet!");
+        return false;
+    }
+    let reader: Box<dyn std::io::BufRead> = if queryfile == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        match std::fs::File::open(queryfile) {
+            Ok(file) => Box::new(std::io::BufReader::new(file)),
+            Err(err) => return handle_file_error(err),
+        }
+    };
+    let mut writer: Box<dyn std::io::Write> = match outfile {
+        Some(name) => match std::fs::File::create(name) {
+            Ok(file) => Box::new(std::io::BufWriter::new(file)),
+            Err(err) => return handle_file_error(err),
+        },
+        None => Box::new(std::io::stdout().lock()),
+    };
+    match datacheck::answer_queries(reader, &mut writer, myboard) {
+        Ok(0) => true,
+        Ok(bad_lines) => {
+            output_string(&format!("Warning: {} malformed query lines.", bad_lines));
+            false
+        }
+        Err(err) => {
+            output_string(&format!("Error: Failed while answering queries: {}", err));
+            false
+        }
+    }
+}
+
+pub fn handle_write_file(myboard: &GameData) -> bool {
+    match file::write_file(myboard) {
+        Ok(()) => true,
+        Err(err) => {
+            output_string(&err.to_string());
+            false
+        }
+    }
+}
+
+// Save the whole game, turn and random numbers included, so --resume carries on from here
+pub fn handle_save(myboard: &GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    let filename = match args_iter.next() {
+        Some(filename) if !filename.starts_with("--") => filename,
+        _ => {
+            output_string("Usage: --save <filename>");
+            return false;
+        }
+    };
+    if !myboard.get_loaded() {
+        output_string("Error: You have not loaded a file yet!");
+        return false;
+    }
+    match save::save_game(myboard, filename) {
+        Ok(()) => {
+            output_string(&format!("Game saved to {} at turn {}.", filename, myboard.get_turn()));
+            true
+        }
+        Err(err) => {
+            output_string(&err.to_string());
+            false
+        }
+    }
+}
+
+// Replace the game with one written by --save
+pub fn handle_resume(myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    let filename = match args_iter.next() {
+        Some(filename) if !filename.starts_with("--") => filename,
+        _ => {
+            output_string("Usage: --resume <filename>");
+            return false;
+        }
+    };
+    match save::resume_game(filename, myboard) {
+        Ok(()) => {
+            let current = myboard.get_current_player();
+            let name = myboard.boards_get_player(current).map(|player| player.get_playername().clone()).unwrap_or_default();
+            output_string(&format!("Game resumed from {} at turn {}, player {} ({}) to move.", filename, myboard.get_turn(), current + 1, name));
+            true
+        }
+        Err(err) => {
+            output_string(&format!("Error: Failed to resume game: {}", err));
+            false
+        }
+    }
+}
+
+// Record every shot from here on in a transcript, the loaded board file is its starting position
+pub fn handle_transcript(myboard: &mut GameData,
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    let filename = match args_iter.next() {
+        Some(filename) if !filename.starts_with("--") => filename,
+        _ => {
+            output_string("Usage: --transcript <filename>");
+            return false;
+        }
+    };
+    if !myboard.get_loaded() {
+        output_string("Error: You have not loaded a file yet!");
+        return false;
+    }
+    if myboard.get_turn() > 0 {
+        output_string("Error: Start the transcript before the first shot, the board file has to be where the game began.");
+        return false;
+    }
+    match transcript::start_transcript(filename, myboard, &myboard.get_filename()) {
+        Ok(()) => {
+            myboard.set_transcript(Some(filename.to_string()));
+            output_string(&format!("Recording moves to {}.", filename));
+            true
+        }
+        Err(err) => {
+            output_string(&err.to_string());
+            false
+        }
+    }
+}
+
+// Step through a transcript from its starting boards
+pub fn handle_replay(args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>) -> bool {
+    let filename = match args_iter.next() {
+        Some(filename) if !filename.starts_with("--") => filename,
+        _ => {
+            output_string("Usage: --replay <transcript>");
+            return false;
+        }
+    };
+    match transcript::load_replay(filename) {
+        Ok(mut replay) => {
+            interactive::run_replay(&mut replay);
+            true
+        }
+        Err(err) => {
+            output_string(&format!("Error: Failed to replay {}: {}", filename, err));
+            false
+        }
+    }
+}
+
+pub fn handle_place_ship(myboard: &mut GameData, 
+    args_iter: &mut std::iter::Peekable<std::iter::Skip<std::slice::Iter<String>>>,
+    mystate: &[StateCreate]) -> bool{
+
+    if !mystate.contains(&StateCreate::StateCreate) || !mystate.contains(&StateCreate::StatePlayer) {
+        output_string("Error: Called Ships without a valid create and file path or without a player.");
+        return false;
+    }
+    let mut ship_data = Vec::new();
+    for next_guess in args_iter.by_ref() {
+        if next_guess.starts_with("--") {
+            output_string("Error: No status for the new ship.");
+            return false;
+        }
+        if next_guess.contains(":") {
+            ship_data.push(next_guess.clone());
+        } else {
+            output_string("Error: Invalid format for ship placement.");
+            return false;
+        }
+    }
+    process_ship_data(ship_data, myboard);
+    true
+}
+
+fn process_ship_data(ship_data: Vec<String>, myboard: &mut GameData) -> bool {
+    for ship in ship_data {
+        let ship_coords: Vec<&str> = ship.split(':').collect();
+        if ship_coords.len() != 3 {
+            output_string("Error: Invalid ship format.");
+            return false;
+        }
+
+        // Parse Ship ID
+        let ship_id = match utils::parse_to_usize(ship_coords[0]) {
+            Ok(ship_tmp) => {
+                let (smallest, largest) = myboard.get_shipsizes();
+                if ship_tmp >= smallest && ship_tmp <= largest {
+                    ship_tmp
+                } else {
+                    output_string("Error: Ship size out of allowed range.");
+                    return false;
+                }
+            }
+            Err(_) => {
+                output_string("Error: Invalid ship size.");
+                return false;
+            }
+        };
+
+        // Parse Coordinates
+        let start: Coord = match ship_coords[1].parse() {
+            Ok(coord) => coord,
+            Err(err) => {
+                match err {
+                    BattleshipError::InvalidRow(_) => output_string("Error: Invalid row"),
+                    BattleshipError::InvalidColumn(_) => output_string("Error: Invalid column"),
+                    BattleshipError::OutOfBounds(_) => output_string("Error: Out of bounds!!"),
+                    _ => output_string("Error: Invalid coordinate format."),
+                }
+                return false;
+            }
+        };
+        // Parse Direction
+        let up_ship = ship_coords[2].to_uppercase();
+        let direction = match up_ship.as_str() {
+            "V" => Direction::Vertical,
+            "H" => Direction::Horizontal,
+            _ => {
+                output_string("Error: Invalid direction.");
+                return false;
+            }
+        };
+
+        // Retrieve Last Player
+        let mut myplayer = match myboard.boards_pop_last() {
+            Some(player) => player,
+            None => {
+                output_string("Error: No player available.");
+                return false;
+            }
+        };
+
+        // Create Ship
+        let placed = ShipBoundingBox::new(ship_id, start, direction, myboard, &myplayer)
+            .map_err(BattleshipError::from)
+            .and_then(|new_ship| myplayer.add_ship(new_ship));
+        myboard.boards_add(myplayer);                   // The player goes back even when the ship does not fit
+        if let Err(err) = placed {
+            output_string(&err.to_string());
+            return false;
+        }
+    }
+    
+    true
+}
@@ -0,0 +1,199 @@
+use battleship::board::GameData;
+use crate::cli::output::output_string;
+use battleship::file;
+use battleship::datacheck;
+use crate::cli::handler;
+use battleship::transcript;
+use battleship::transcript::Replay;
+use std::io;
+
+
+// This is still present for interactive mode but out of date on most commands.
+fn parse_command(mybuf: &str, myboard: &mut GameData) -> bool {
+    let upper = mybuf.to_uppercase();
+    let mut tokens = upper.split_whitespace();
+    let command = tokens.next();
+    match command {                                         // Tokenize and split the command
+        Some("LOAD") => {
+            if myboard.get_loaded() {
+                output_string("A previous board was loaded, now loading new file.");
+                handler::reset_board(myboard);
+            }
+            if let Some(filename) = mybuf.split_whitespace().nth(1) {       // Keep the case of the filename
+                output_string(&format!("Loading the file <{}>", filename));
+                match file::load_file(filename, myboard) {
+                    Ok(_) => {
+                        handler::print_warnings(myboard);
+                        output_string("File loaded successfully")
+                    },
+                    Err(err) => output_string(&format!("File failed to load: {}.", err)),
+                }
+            } else {
+                output_string("Usage: --load <filename>");
+            }
+            false
+        }
+        Some("GUESS") => {
+            if !myboard.get_loaded() {        // Not initialized with a load
+                output_string("You have not loaded a file yet.");
+                return false;
+            }
+            let mut results = Vec::new();
+            let mut oob_messages = Vec::new();
+
+            for tok in tokens {
+                let mut tmp_id: usize = 0;
+                let guesses: Vec<&str> = tok.split(',').collect();
+                for (guess_num, guess) in guesses.iter().enumerate() {
+                    if guess_num == 0 {                     // First entry is always the player
+                        match guess.parse::<usize>() {
+                            Ok(n) => {
+                                if n > myboard.get_boards_len() || n < 1 {
+                                    output_string(&format!("Error: Invalid playerid, {}", n));
+                                    return false;
+                                }
+                                else {
+                                    tmp_id = n;
+                                }
+                            }
+                            Err(_) => {
+                                output_string(&format!("Error: Invalid playerid, {}", guess));
+                                return false;
+                            }
+                        }
+                        continue;
+                    }
+                    match datacheck::query_player(myboard, tmp_id, guess) { // Call query_player directly
+                        Ok(value) => results.push(value.to_string()),
+                        Err(msg) => {
+                            results.push("OOB".to_string());
+                            oob_messages.push(msg.to_string());
+                        }
+                    }
+                }
+            }
+
+            if !oob_messages.is_empty() {
+                for msg in oob_messages {
+                    output_string(&msg);
+                }
+            }
+            output_string(&format!("Results are {}", results.join(",")));
+            false
+        },
+        Some("AI") => {
+            match mybuf.split_whitespace().nth(1) {
+                Some(level) => { handler::set_ai_level(myboard, level); },
+                None => output_string(&format!("AI level is {} ({}).", myboard.get_ai_level().get_level(), myboard.get_ai_level().get_name())),
+            }
+            false
+        }
+        Some("AISHOT") => {
+            match tokens.next() {
+                Some(player) => { handler::ai_shot(myboard, player); },
+                None => output_string("Usage: --aishot <player>"),
+            }
+            false
+        }
+        Some("SEED") => {
+            match tokens.next().map(|seed| seed.parse::<u64>()) {
+                Some(Ok(seed)) => {
+                    myboard.set_seed(seed);
+                    output_string(&format!("Seed set to {}.", seed));
+                }
+                Some(Err(_)) => output_string("Usage: --seed <number>"),
+                None => output_string(&format!("Seed is {}.", myboard.get_seed())),
+            }
+            false
+        }
+        Some("HELP") => {
+            output_string("Available commands: --load <filename>\n--guess <list in A1 or AA10 format>\n--ai <level>\n--aishot <player>\n--seed <number>\n--help this output\n--exit or --quit to quit.");
+            false
+        }
+        Some("EXIT") | Some("QUIT") => {
+            output_string("Thank you for enjoying Battleship Test in Rust!");
+            true
+        }
+        None => {
+            output_string("No command was recognized type --help for a list of commands");
+            false
+        }
+        _ => {                  // Handle only commands that are not known commands 1 is default player
+            match datacheck::query_array(upper.as_str(), myboard) {
+                Ok(value) => output_string(&format!("{}", value)),
+                Err(msg) => output_string(&msg.to_string()),
+            }
+            false
+        }
+    }
+}
+
+
+// This command is called in interactive mode from main.
+pub fn eval_input(mybuf: String, myboard: &mut GameData) -> bool {
+    if mybuf.contains("--") {
+        let tokens = mybuf.split("--").filter(|&x| !x.is_empty());     // Split on token command
+        for tok in tokens {
+            if parse_command(tok, myboard) {
+                return true;
+            }
+        }
+    } else {
+        if parse_command(mybuf.as_str(), myboard) {
+            return true;
+        }
+    }
+    false
+}
+// Step through a replay from stdin until quit or the end of input.  A blank line is next
+pub fn run_replay(replay: &mut Replay) {
+    let transcript = replay.get_transcript();
+    output_string(&format!("Replaying {} moves from {}. Commands: next, prev, goto <move>, show, help, quit.",
+        replay.len(), transcript.get_tag("Board").unwrap_or("")));
+    show_position(replay);
+    loop {
+        let mut buffer = String::new();
+        match io::stdin().read_line(&mut buffer) {
+            Ok(0) | Err(_) => break,                                    // End of input
+            Ok(_) => {}
+        }
+        let lower = buffer.trim().to_lowercase();
+        let mut tokens = lower.split_whitespace();
+        let moved = match tokens.next() {
+            None | Some("next") | Some("n") => replay.forward(),
+            Some("prev") | Some("p") => replay.back(),
+            Some("goto") | Some("g") => match tokens.next().map(|num| num.parse::<usize>()) {
+                Some(Ok(position)) => replay.goto(position).map(|_| true),
+                _ => {
+                    output_string("Usage: goto <move number, 0 for the start>");
+                    continue;
+                }
+            },
+            Some("show") | Some("s") => Ok(true),
+            Some("help") | Some("h") => {
+                output_string("next (or a blank line), prev, goto <move number, 0 for the start>, show, quit");
+                continue;
+            }
+            Some("quit") | Some("exit") | Some("q") => break,
+            Some(other) => {
+                output_string(&format!("Unknown replay command {}, type help for a list.", other));
+                continue;
+            }
+        };
+        match moved {
+            Ok(true) => show_position(replay),
+            Ok(false) if replay.get_position() == 0 => output_string("Already at the start of the game."),
+            Ok(false) => output_string("Already at the end of the game."),
+            Err(err) => output_string(&err.to_string()),
+        }
+    }
+}
+
+// The move just played and every board as it stands after it
+fn show_position(replay: &Replay) {
+    match replay.last_move() {
+        Some(report) => output_string(&format!("Move {}/{}: {}", replay.get_position(), replay.len(), transcript::format_move(report))),
+        None => output_string(&format!("Start of the game, {} moves.", replay.len())),
+    }
+    handler::display_boards(replay.get_game(), true);
+}
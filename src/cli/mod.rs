@@ -0,0 +1,4 @@
+pub mod commandline;
+pub mod handler;
+pub mod interactive;
+pub mod output;
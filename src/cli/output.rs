@@ -0,0 +1,14 @@
+use std::fmt::Display;
+use std::io;
+
+// Function that allows for consistent output--Pretty
+pub fn output_string<T: Display + ?Sized>(buf: &T) {
+    let x = format!(":> {}\n:> ", buf);
+    print!("{}", x);
+}
+
+// Handling repetitive file errors
+pub fn handle_file_error(err: io::Error) -> bool {
+    output_string(&format!("Error: Failed to open specified file: {}", err));
+    false
+}
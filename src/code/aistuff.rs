@@ -1,28 +1,214 @@
-use std::collections::HashMap;
-
 use crate::code::board;
-use crate::code::enums::HitMiss;
-use crate::code::enums::Direction;
-use crate::code::utils::base_26;
+use crate::code::board::{PlayBoard, ShipBoundingBox};
+use crate::code::coord::Coord;
+use crate::code::enums::{Direction, Intelligence};
+use crate::code::strategy::{CellState, ObservationBoard, Strategy};
+use crate::code::utils;
+use crate::code::utils::GameRng;
+use rand::Rng;
 
-use super::board::GameData;
+// Weight given to a placement for every unsunk hit it passes through, keeps the AI finishing ships it found
+const HIT_WEIGHT: usize = 20;
+// Fleets drawn per shot by the Monte Carlo tier, and attempts to place each ship within a fleet
+const MONTE_CARLO_SAMPLES: usize = 400;
+const MONTE_CARLO_ATTEMPTS: usize = 40;
 
 pub struct AiBoard {
     myplayer: board::PlayBoard,
-    myheatmap: HashMap<String, HitMiss>,
+    myheatmap: Vec<Vec<usize>>,                     // Row, Col counts of possible placements
     intelligence: usize,
 }
 
 impl AiBoard {
     pub fn new(myplayer: board::PlayBoard, intelligence: usize) -> Self {
         Self {
-            myplayer: myplayer,
-            myheatmap: HashMap::new(),
-            intelligence: usize,
+            myplayer,
+            myheatmap: Vec::new(),
+            intelligence,
+        }
+    }
+
+    pub fn get_player(&self) -> &PlayBoard {
+        &self.myplayer
+    }
+
+    pub fn get_heat_map(&self) -> &Vec<Vec<usize>> {
+        &self.myheatmap
+    }
+
+    pub fn get_intelligence(&self) -> Intelligence {
+        Intelligence::from_level(self.intelligence)
+    }
+
+    pub fn set_intelligence(&mut self, level: Intelligence) {
+        self.intelligence = level.get_level();
+    }
+
+    // Count every placement of every ship still afloat on the target that agrees with the shots so far.
+    // Misses and sunk ships block placements, placements through unsunk hits are weighted up.
+    pub fn create_heat_map(&mut self, observed: &ObservationBoard) {
+        let (max_col, max_row) = observed.get_col_row();
+        self.myheatmap = vec![vec![0; max_col]; max_row];
+
+        let sunk_ships: &[ShipBoundingBox] = observed.get_sunk_ships();
+        for &ship_size in observed.get_remaining_sizes() {
+            for row in 0..max_row {
+                for col in 0..max_col {
+                    for direction in [Direction::Horizontal, Direction::Vertical] {
+                        let start = Coord { row, col };
+                        if !observed.in_bounds(start.step(direction, ship_size - 1)) {
+                            continue;
+                        }
+                        if sunk_ships.iter().any(|ship| ship.overlap_possible(ship_size, start, &direction)) {
+                            continue;
+                        }
+                        let points: Vec<Coord> = start.line(direction, ship_size).collect();
+                        if points.iter().any(|&point| observed.get_cell(point) == CellState::Miss) {
+                            continue;
+                        }
+                        let hits = points.iter().filter(|&&point| observed.get_cell(point) == CellState::Hit).count();
+                        let weight = 1 + hits * HIT_WEIGHT;
+                        for point in points {
+                            if observed.get_cell(point) == CellState::Unknown {
+                                self.myheatmap[point.row][point.col] += weight;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Draw random fleets of the ships still afloat, keeping the ones that agree with every shot so far,
+    // and count how often each cell is covered.  Returns False when no fleet could be drawn.
+    pub fn create_monte_carlo_map(&mut self, observed: &ObservationBoard, rng: &mut GameRng) -> bool {
+        let (max_col, max_row) = observed.get_col_row();
+        self.myheatmap = vec![vec![0; max_col]; max_row];
+        let open_hits: Vec<Coord> = observed.cells_in_state(CellState::Hit);
+        let mut found = false;
+
+        for _ in 0..MONTE_CARLO_SAMPLES {
+            let mut taken = vec![vec![false; max_col]; max_row];
+            let mut placed_all = true;
+            for &ship_size in observed.get_remaining_sizes() {
+                let mut placed = false;
+                for _ in 0..MONTE_CARLO_ATTEMPTS {
+                    let direction = if rng.random_range(0..2) == 0 { Direction::Horizontal } else { Direction::Vertical };
+                    let start = Coord { col: rng.random_range(0..max_col), row: rng.random_range(0..max_row) };
+                    if !observed.in_bounds(start.step(direction, ship_size - 1)) {
+                        continue;
+                    }
+                    let points: Vec<Coord> = start.line(direction, ship_size).collect();
+                    if points.iter().all(|&point| !taken[point.row][point.col] && matches!(observed.get_cell(point), CellState::Unknown | CellState::Hit)) {
+                        for point in points {
+                            taken[point.row][point.col] = true;
+                        }
+                        placed = true;
+                        break;
+                    }
+                }
+                if !placed {
+                    placed_all = false;
+                    break;
+                }
+            }
+            if !placed_all || open_hits.iter().any(|point| !taken[point.row][point.col]) {
+                continue;                                   // Fleet does not explain the hits
+            }
+            found = true;
+            for (row, cols) in taken.iter().enumerate() {
+                for (col, &is_taken) in cols.iter().enumerate() {
+                    if is_taken && observed.get_cell(Coord { row, col }) == CellState::Unknown {
+                        self.myheatmap[row][col] += 1;
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    // Pick the next shot for the current intelligence
+    pub fn choose_shot(&mut self, observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord> {
+        match self.get_intelligence() {
+            Intelligence::Random => pick_unknown(observed, rng, |_| true),
+            Intelligence::HuntTarget => {
+                target_shot(observed, rng).or_else(|| pick_unknown(observed, rng, |_| true))
+            }
+            Intelligence::ParityTarget => {
+                let parity = observed.get_remaining_sizes().iter().copied().min().unwrap_or(2).max(2);
+                target_shot(observed, rng)
+                    .or_else(|| pick_unknown(observed, rng, |coord| (coord.col + coord.row) % parity == 0))
+                    .or_else(|| pick_unknown(observed, rng, |_| true))
+            }
+            Intelligence::Probability => {
+                self.create_heat_map(observed);
+                self.pick_hottest(observed, rng)
+            }
+            Intelligence::MonteCarlo => {
+                if !self.create_monte_carlo_map(observed, rng) {
+                    self.create_heat_map(observed);             // Too constrained to sample, fall back to counting
+                }
+                self.pick_hottest(observed, rng)
+            }
         }
     }
 
-    pub fn create_heat_map(&self, myboard: &mut board::GameData) {
-        // Still need to do this.
+    // The untried cell with the highest count, ties are broken at random
+    fn pick_hottest(&self, observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord> {
+        let mut best = 0;
+        let mut candidates: Vec<Coord> = Vec::new();
+        for (row, counts) in self.myheatmap.iter().enumerate() {
+            for (col, &count) in counts.iter().enumerate() {
+                if observed.get_cell(Coord { row, col }) != CellState::Unknown {
+                    continue;
+                }
+                if count > best {
+                    best = count;
+                    candidates.clear();
+                }
+                if count == best {
+                    candidates.push(Coord { row, col });
+                }
+            }
+        }
+        utils::pick_random(&candidates, rng).copied()
+    }
+}
+
+// Random untried cell that passes the filter
+fn pick_unknown<F: Fn(Coord) -> bool>(observed: &ObservationBoard, rng: &mut GameRng, filter: F) -> Option<Coord> {
+    let candidates: Vec<Coord> = observed.cells_in_state(CellState::Unknown)
+        .into_iter()
+        .filter(|&coord| filter(coord))
+        .collect();
+    utils::pick_random(&candidates, rng).copied()
+}
+
+// Target mode: shoot next to hits on ships still afloat, preferring to extend a line of two hits
+fn target_shot(observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord> {
+    let open = observed.state_mask(CellState::Hit);
+    let unknown = observed.state_mask(CellState::Unknown);
+    // A hit with a hit behind it, moved one further along the same line
+    let in_line: Vec<Coord> = open.intersect(&open.shift_right()).shift_right()
+        .union(&open.intersect(&open.shift_left()).shift_left())
+        .union(&open.intersect(&open.shift_down()).shift_down())
+        .union(&open.intersect(&open.shift_up()).shift_up())
+        .intersect(&unknown)
+        .iter()
+        .collect();
+    if !in_line.is_empty() {
+        return utils::pick_random(&in_line, rng).copied();
+    }
+    let neighbours: Vec<Coord> = open.neighbours().intersect(&unknown).iter().collect();
+    utils::pick_random(&neighbours, rng).copied()
+}
+
+impl Strategy for AiBoard {
+    fn get_name(&self) -> String {
+        self.get_intelligence().get_name().to_string()
+    }
+
+    fn choose_shot(&mut self, observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord> {
+        AiBoard::choose_shot(self, observed, rng)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,170 @@
+use crate::code::board::{GameData, PlayBoard};
+use crate::code::error::BattleshipError;
+use crate::code::session::GameSession;
+use crate::code::strategy::Strategy;
+use crate::code::utils;
+use crate::code::utils::GameRng;
+
+// Stop a game that makes no progress, every cell of every board has been shot by then
+const TURN_LIMIT_PER_CELL: usize = 4;
+
+// Results for one strategy over every game of an arena run
+#[derive(Clone, Debug)]
+pub struct ArenaStats {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub shots_to_win: Vec<usize>,                   // Shots fired by this strategy in each game it won
+}
+
+impl ArenaStats {
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.games as f64
+    }
+
+    // Wilson score interval for the win rate at 95%
+    pub fn win_rate_interval(&self) -> (f64, f64) {
+        if self.games == 0 {
+            return (0.0, 0.0);
+        }
+        let z = 1.96;
+        let n = self.games as f64;
+        let p = self.win_rate();
+        let centre = p + z * z / (2.0 * n);
+        let spread = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+        let denom = 1.0 + z * z / n;
+        ((centre - spread) / denom, (centre + spread) / denom)
+    }
+
+    pub fn mean_shots(&self) -> Option<f64> {
+        if self.shots_to_win.is_empty() {
+            return None;
+        }
+        Some(self.shots_to_win.iter().sum::<usize>() as f64 / self.shots_to_win.len() as f64)
+    }
+
+    // Normal approximation of the 95% interval around the mean shots to win
+    pub fn mean_shots_interval(&self) -> Option<(f64, f64)> {
+        let mean = self.mean_shots()?;
+        let n = self.shots_to_win.len() as f64;
+        if n < 2.0 {
+            return Some((mean, mean));
+        }
+        let variance = self.shots_to_win.iter()
+            .map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>() / (n - 1.0);
+        let margin = 1.96 * (variance / n).sqrt();
+        Some((mean - margin, mean + margin))
+    }
+
+    // Nearest rank percentile, 50 is the median
+    pub fn percentile_shots(&self, percent: usize) -> Option<usize> {
+        if self.shots_to_win.is_empty() {
+            return None;
+        }
+        let mut sorted = self.shots_to_win.clone();
+        sorted.sort_unstable();
+        let rank = (percent * sorted.len()).div_ceil(100).max(1);
+        Some(sorted[rank.min(sorted.len()) - 1])
+    }
+}
+
+// Summary of a whole arena run
+#[derive(Clone, Debug)]
+pub struct ArenaReport {
+    pub seed: u64,
+    pub games: usize,
+    pub unfinished: usize,                          // Games stopped by the turn limit or a strategy with no shot
+    pub stats: Vec<ArenaStats>,
+}
+
+// Play games between the strategies on boards shaped like myboard.  The seating steps through every order of
+// the strategies, so over n! games each one sits in every seat and next to every opponent equally often
+pub fn run_arena(myboard: &GameData, strategies: &mut [Box<dyn Strategy>], games: usize) -> Result<ArenaReport, BattleshipError> {
+    if strategies.len() < 2 {
+        return Err(BattleshipError::NotEnoughStrategies);
+    }
+    let mut report = ArenaReport {
+        seed: myboard.get_seed(),
+        games,
+        unfinished: 0,
+        stats: strategies.iter().map(|player| ArenaStats {
+            name: player.get_name(),
+            games,
+            wins: 0,
+            shots_to_win: Vec::new(),
+        }).collect(),
+    };
+
+    let mut rng = utils::rng_from_seed(myboard.get_seed());     // One generator for every game in the run
+    let mut seats: Vec<usize> = (0..strategies.len()).collect();    // Seat s is played by strategy seats[s]
+    for game_num in 0..games {
+        if game_num > 0 {
+            next_seating(&mut seats);
+        }
+        let mut game = arena_game(myboard, &seats, strategies, &mut rng)?;
+        game.set_rng(rng);
+        let (cols, rows) = game.get_col_row();
+        let turn_limit = cols * rows * seats.len() * TURN_LIMIT_PER_CELL;
+        let mut session = GameSession::new(game)?;
+        let mut shots = vec![0; seats.len()];
+
+        while !session.is_over() && session.get_turn() < turn_limit {
+            let seat = session.get_current_player();
+            match session.play_turn(strategies[seats[seat]].as_mut()) {
+                Ok(_) => shots[seat] += 1,
+                Err(_) => break,
+            }
+        }
+        match session.result() {
+            Some(result) => {
+                let stats = &mut report.stats[seats[result.winner]];
+                stats.wins += 1;
+                stats.shots_to_win.push(shots[result.winner]);
+            }
+            None => report.unfinished += 1,
+        }
+        rng = session.into_game().take_rng();
+    }
+    Ok(report)
+}
+
+// The next seating in lexicographic order, back to the first after the last
+pub fn next_seating(seats: &mut [usize]) {
+    let pivot = match (1..seats.len()).rev().find(|&index| seats[index - 1] < seats[index]) {
+        Some(index) => index - 1,
+        None => {
+            seats.reverse();                                // Last order, start again
+            return;
+        }
+    };
+    let swap = (pivot + 1..seats.len()).rev().find(|&index| seats[index] > seats[pivot]).unwrap_or(pivot);
+    seats.swap(pivot, swap);
+    seats[pivot + 1..].reverse();
+}
+
+// A fresh game with a fleet per seat, placed by the strategy in it
+fn arena_game(myboard: &GameData, seats: &[usize], strategies: &mut [Box<dyn Strategy>], rng: &mut GameRng) -> Result<GameData, BattleshipError> {
+    let mut game = GameData::default();
+    let (cols, rows) = myboard.get_col_row();
+    let (small, large) = myboard.get_shipsizes();
+    game.set_col_row(cols, rows);
+    game.set_shipsizes(small, Some(large))?;
+    game.set_playercount(seats.len());
+    for (seat, &strategy_num) in seats.iter().enumerate() {
+        let mut player = PlayBoard::default();
+        player.set_playername(format!("Seat {} (strategy {})", seat + 1, strategy_num + 1));
+        player.set_playernum(seat);
+        let strategy = &mut strategies[strategy_num];
+        strategy.place_fleet(&game, &mut player, rng)?;
+        if (small..=large).any(|ship_id| !player.player_has_ship_id(ship_id)) || player.return_ships().len() != large - small + 1 {
+            return Err(BattleshipError::IncompleteFleet(strategy.get_name()));
+        }
+        game.boards_add(player);
+    }
+    game.set_loaded(true);
+    Ok(game)
+}
@@ -0,0 +1,192 @@
+use crate::code::coord::Coord;
+
+// One bit per cell, row major, packed into u64 words.  Bits past the last cell are always 0
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct BitBoard {
+    cols: usize,
+    rows: usize,
+    words: Vec<u64>,
+}
+
+impl BitBoard {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            words: vec![0; (cols * rows).div_ceil(64)],
+        }
+    }
+
+    // Every cell on the board set
+    pub fn full(cols: usize, rows: usize) -> Self {
+        let mut board = BitBoard::new(cols, rows);
+        for word in board.words.iter_mut() {
+            *word = u64::MAX;
+        }
+        board.trim();
+        board
+    }
+
+    pub fn get_col_row(&self) -> (usize, usize) {
+        (self.cols, self.rows)
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        coord.col < self.cols && coord.row < self.rows
+    }
+
+    fn index(&self, coord: Coord) -> usize {
+        coord.row * self.cols + coord.col
+    }
+
+    // Returns False when the cell is off the board
+    pub fn set(&mut self, coord: Coord) -> bool {
+        if !self.in_bounds(coord) {
+            return false;
+        }
+        let index = self.index(coord);
+        self.words[index / 64] |= 1 << (index % 64);
+        true
+    }
+
+    pub fn clear(&mut self, coord: Coord) {
+        if self.in_bounds(coord) {
+            let index = self.index(coord);
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    pub fn test(&self, coord: Coord) -> bool {
+        if !self.in_bounds(coord) {
+            return false;
+        }
+        let index = self.index(coord);
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn union(&self, other: &BitBoard) -> BitBoard {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersect(&self, other: &BitBoard) -> BitBoard {
+        self.combine(other, |a, b| a & b)
+    }
+
+    // Cells in self that are not in other
+    pub fn without(&self, other: &BitBoard) -> BitBoard {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    pub fn invert(&self) -> BitBoard {
+        let mut result = self.clone();
+        for word in result.words.iter_mut() {
+            *word = !*word;
+        }
+        result.trim();
+        result
+    }
+
+    fn combine<F: Fn(u64, u64) -> u64>(&self, other: &BitBoard, op: F) -> BitBoard {
+        let mut result = self.clone();
+        for (word, &other_word) in result.words.iter_mut().zip(other.words.iter()) {
+            *word = op(*word, other_word);
+        }
+        result
+    }
+
+    // Every cell moved one column right, cells falling off the edge are dropped and nothing wraps a row
+    pub fn shift_right(&self) -> BitBoard {
+        self.shift_bits(1, true).without(&self.column_mask(0))
+    }
+
+    pub fn shift_left(&self) -> BitBoard {
+        self.shift_bits(1, false).without(&self.column_mask(self.cols.saturating_sub(1)))
+    }
+
+    pub fn shift_down(&self) -> BitBoard {
+        self.shift_bits(self.cols, true)
+    }
+
+    pub fn shift_up(&self) -> BitBoard {
+        self.shift_bits(self.cols, false)
+    }
+
+    // Cells next to any set cell, not counting the set cells themselves
+    pub fn neighbours(&self) -> BitBoard {
+        self.shift_left()
+            .union(&self.shift_right())
+            .union(&self.shift_up())
+            .union(&self.shift_down())
+            .without(self)
+    }
+
+    // Every set cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.words.iter().enumerate().flat_map(move |(word_num, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(word_num * 64 + bit)
+            })
+        })
+        .map(move |index| Coord { row: index / self.cols, col: index % self.cols })
+    }
+
+    fn column_mask(&self, col: usize) -> BitBoard {
+        let mut mask = BitBoard::new(self.cols, self.rows);
+        for row in 0..self.rows {
+            mask.set(Coord { row, col });
+        }
+        mask
+    }
+
+    // Move every bit amount places towards the end of the board, or towards the start
+    fn shift_bits(&self, amount: usize, towards_end: bool) -> BitBoard {
+        let mut result = BitBoard::new(self.cols, self.rows);
+        let word_shift = amount / 64;
+        let bit_shift = amount % 64;
+        let len = self.words.len();
+        for i in 0..len {
+            let value = if towards_end {
+                let low = i.checked_sub(word_shift).map(|j| self.words[j] << bit_shift).unwrap_or(0);
+                let carry = match (i.checked_sub(word_shift + 1), bit_shift) {
+                    (Some(j), shift) if shift > 0 => self.words[j] >> (64 - shift),
+                    _ => 0,
+                };
+                low | carry
+            } else {
+                let high = self.words.get(i + word_shift).map(|&w| w >> bit_shift).unwrap_or(0);
+                let carry = match (self.words.get(i + word_shift + 1), bit_shift) {
+                    (Some(&w), shift) if shift > 0 => w << (64 - shift),
+                    _ => 0,
+                };
+                high | carry
+            };
+            result.words[i] = value;
+        }
+        result.trim();
+        result
+    }
+
+    // Keep the bits past the last cell at 0
+    fn trim(&mut self) {
+        let cells = self.cols * self.rows;
+        if !cells.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1u64 << (cells % 64)) - 1;
+            }
+        }
+    }
+}
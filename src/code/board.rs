@@ -1,27 +1,24 @@
 use crate::code::enums::Direction;
 use std::collections::HashSet;
 use std::collections::HashMap;
-use crate::code::enums::HitMiss;
+use crate::code::enums::{FileFormat, HitMiss, Intelligence, ShotResult};
+use crate::code::error::{BattleshipError, PlacementError};
+use crate::code::bitboard::BitBoard;
+use crate::code::coord::Coord;
+use crate::code::session::TurnReport;
+use crate::code::utils;
+use crate::code::utils::GameRng;
 
 // This structure will be the main board per player
+#[derive(Default, Clone, PartialEq, Debug)]
 pub struct PlayBoard {
     playername: String,
     playernum: usize,
     ships: Vec<ShipBoundingBox>,                    // This is used in create to store only ships
     ship_ids: HashSet<usize>,
-    guesses: HashMap<String, HitMiss>,
-}
-
-impl Default for PlayBoard {
-    fn default() -> Self {
-        Self {
-            playername: String::new(),
-            playernum: 0,
-            ships: Vec::new(),
-            ship_ids: HashSet::new(),
-            guesses: HashMap::new(),
-        }
-    }
+    cells: HashMap<Coord, usize>,                   // Every ship segment to its index in ships
+    hits: BitBoard,                                 // Guesses, sized when the board joins a GameData
+    misses: BitBoard,
 }
 
 impl PlayBoard {
@@ -49,40 +46,90 @@ impl PlayBoard {
         self.ship_ids.contains(&new_ship_id)
     }
 
-    pub fn add_ship(&mut self, new_ship: ShipBoundingBox) -> bool {
+    pub fn add_ship(&mut self, new_ship: ShipBoundingBox) -> Result<(), BattleshipError> {
         if self.player_has_ship_id(new_ship.ship_id) {       // Make sure that this new ship doesn't have the same id as another
-                return false;
+                return Err(PlacementError::DuplicateId { ship_id: new_ship.ship_id }.into());
         }
 
+        for point in new_ship.get_points() {
+            self.cells.insert(point, self.ships.len());
+        }
+        self.ship_ids.insert(new_ship.get_ship_id());
         self.ships.push(new_ship);
-        self.ship_ids.push(new_ship.get_ship_id());
-        true
+        Ok(())
+    }
+
+    // Removing a ship shifts the ones after it, so the cell index is rebuilt
+    fn rebuild_cells(&mut self) {
+        self.cells.clear();
+        for (index, ship) in self.ships.iter().enumerate() {
+            for point in ship.get_points() {
+                self.cells.insert(point, index);
+            }
+        }
+    }
+
+    // The ship covering coord
+    pub fn ship_at(&self, coord: Coord) -> Option<&ShipBoundingBox> {
+        self.cells.get(&coord).map(|&index| &self.ships[index])
     }
 
     pub fn return_ships(&self) -> &Vec<ShipBoundingBox> {
         &self.ships
     }
 
-    pub fn check_collision(&self, start: (usize, usize), ship_size: usize, direction: Direction) -> bool {
-        let (start_col, start_row) = start;
-
-        for ship in &self.ships {
-            if ship.overlap_possible(ship_size, start, &direction) {
-                return true;
-            }
-        }
+    // True if a ship of ship_size at start would cover any existing ship
+    pub fn check_collision(&self, start: Coord, ship_size: usize, direction: Direction) -> bool {
+        self.find_collision(start, ship_size, direction).is_some()
+    }
 
-        false // No collision
+    // The first existing ship a ship of ship_size at start would cover, as its id and the shared cell
+    pub fn find_collision(&self, start: Coord, ship_size: usize, direction: Direction) -> Option<(usize, Coord)> {
+        start.line(direction, ship_size)
+            .find_map(|point| self.ship_at(point).map(|ship| (ship.ship_id, point)))
     }
 
     // Check if any guess is a hit, if so return ship_id for the hit.
-    pub fn handle_shot(&self, row: usize, col: usize) -> Option<usize>{
-        for ship in &self.ships {
-            if ship.point_in_ship(row, col) {       // Hit
-                return Some(ship.ship_id);
-            }
+    pub fn handle_shot(&self, coord: Coord) -> Option<usize>{
+        self.ship_at(coord).map(|ship| ship.ship_id)
+    }
+
+// Apply a shot to the fleet.  Sunk is only reported on the shot that finishes a ship
+    pub fn receive_shot(&mut self, coord: Coord) -> ShotResult {
+        let ship = match self.cells.get(&coord) {
+            Some(&index) => &mut self.ships[index],
+            None => return ShotResult::Miss,
+        };
+        let was_sunk = ship.is_sunk();
+        ship.register_hit(coord);
+        if !was_sunk && ship.is_sunk() {
+            return ShotResult::Sunk(ship.ship_id);
         }
-        None                                // Miss
+        ShotResult::Hit(ship.ship_id)
+    }
+
+    // Record a guess at coord and resolve it against the fleet, an error if it was already guessed
+    pub fn take_shot(&mut self, coord: Coord) -> Result<ShotResult, BattleshipError> {
+        let hit_miss = if self.handle_shot(coord).is_some() { HitMiss::Hit } else { HitMiss::Miss };
+        self.add_guess(coord, hit_miss)?;
+        Ok(self.receive_shot(coord))
+    }
+
+    pub fn all_ships_sunk(&self) -> bool {
+        self.ships.iter().all(|ship| ship.is_sunk())
+    }
+
+    pub fn ships_remaining(&self) -> usize {
+        self.ships.iter().filter(|ship| !ship.is_sunk()).count()
+    }
+
+// Remove a ship by id, used to back out a placement
+    pub fn remove_ship(&mut self, ship_id: usize) -> Option<ShipBoundingBox> {
+        let index = self.ships.iter().position(|ship| ship.ship_id == ship_id)?;
+        self.ship_ids.remove(&ship_id);
+        let ship = self.ships.remove(index);
+        self.rebuild_cells();
+        Some(ship)
     }
 
 // Remove the first ship
@@ -90,21 +137,59 @@ impl PlayBoard {
         if self.ships.is_empty() {
             return None;
         }
-        self.ship_ids.remove(self.ships[self.ships[0].get_ship_id()]);
-        Some(self.ships.remove(0))
+        self.ship_ids.remove(&self.ships[0].get_ship_id());
+        let ship = self.ships.remove(0);
+        self.rebuild_cells();
+        Some(ship)
     }
 
-// Add a guess, returns True if added, False if already present
-    pub fn add_guess(&mut self, guess: String, result: enums::HitMiss) -> Bool {
-        if self.guesses.contains_key(&guess) {
-            false
+// Size the guess boards, keeping guesses if the size is unchanged
+    pub fn set_dimensions(&mut self, cols: usize, rows: usize) {
+        if self.hits.get_col_row() != (cols, rows) {
+            self.hits = BitBoard::new(cols, rows);
+            self.misses = BitBoard::new(cols, rows);
         }
-        self.guesses.push(guess, result);
-        true
+    }
+
+    pub fn get_hits(&self) -> &BitBoard {
+        &self.hits
+    }
+
+    pub fn get_misses(&self) -> &BitBoard {
+        &self.misses
+    }
+
+// Look up a previous guess at coord
+    pub fn get_guess(&self, coord: Coord) -> Option<HitMiss> {
+        if self.hits.test(coord) {
+            Some(HitMiss::Hit)
+        } else if self.misses.test(coord) {
+            Some(HitMiss::Miss)
+        } else {
+            None
+        }
+    }
+
+// Add a guess at coord, an error if it was already guessed or is off the board
+    pub fn add_guess(&mut self, coord: Coord, result: HitMiss) -> Result<(), BattleshipError> {
+        if self.get_guess(coord).is_some() {
+            return Err(BattleshipError::AlreadyGuessed(coord));
+        }
+        let added = match result {
+            HitMiss::Hit => self.hits.set(coord),
+            HitMiss::Miss => self.misses.set(coord),
+        };
+        if !added {
+            return Err(BattleshipError::OutOfBounds(coord.to_string()));
+        }
+        Ok(())
     }
 }
 
 
+// Rules every game here plays by: ships may touch, one shot a turn, the last fleet afloat wins
+pub const DEFAULT_RULES: &str = "classic";
+
 // This will be the main game data storage.  Boards will only be stored inside a Vector
 pub struct GameData {
     rows: usize,
@@ -112,9 +197,23 @@ pub struct GameData {
     player_count: usize,
     loaded: bool,
     interactive: bool,
+    lenient: bool,                                  // Load files whose player count is off, with warnings
+    warnings: Vec<BattleshipError>,                 // Problems the last lenient load let through
     filename: String,
+    format: FileFormat,                             // Format the boards were loaded from and are written in
+    rules: String,                                  // Rule set named in a v2 file, carried through untouched
     smallestship: usize,
     largestship: usize,
+    fleet_given: bool,                              // The fleet was given or loaded rather than left at the default
+    ai_level: Intelligence,
+    ai_heat_map: Vec<Vec<usize>>,                   // Row, Col counts behind the AI's last shot, empty until it fires
+    seed: u64,
+    seeded: bool,                                   // The seed was given or loaded rather than drawn at random
+    rng: GameRng,                                   // Seeded from seed, shared by every random decision
+    turn: usize,                                    // Shots fired so far
+    current: usize,                                 // Index of the player whose turn it is
+    eliminated: Vec<usize>,                         // Players out of the game, in the order they went out
+    transcript: Option<String>,                     // File every shot is appended to, if one was started
     boards: Vec<PlayBoard>
 }
 
@@ -127,6 +226,22 @@ impl GameData {
         self.interactive
     }
 
+    pub fn set_lenient(&mut self, state: bool) {
+        self.lenient = state;
+    }
+
+    pub fn get_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    pub fn get_warnings(&self) -> &[BattleshipError] {
+        &self.warnings
+    }
+
+    pub fn add_warning(&mut self, warning: BattleshipError) {
+        self.warnings.push(warning);
+    }
+
     pub fn get_loaded(&self) -> bool {
         self.loaded
     }
@@ -143,26 +258,243 @@ impl GameData {
         self.filename = name;
     }
 
+    pub fn get_transcript(&self) -> Option<String> {
+        self.transcript.clone()
+    }
+
+    pub fn set_transcript(&mut self, filename: Option<String>) {
+        self.transcript = filename;
+    }
+
+    pub fn get_format(&self) -> FileFormat {
+        self.format
+    }
+
+    pub fn set_format(&mut self, format: FileFormat) {
+        self.format = format;
+    }
+
+    pub fn get_rules(&self) -> &str {
+        &self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: String) {
+        self.rules = rules;
+    }
+
     pub fn get_shipsizes(&self) -> (usize, usize) {
         (self.smallestship, self.largestship)
     }
 
-    pub fn set_shipsizes(&mut self, small: usize, large: Option<usize>) -> Result<(), &str> {
+    pub fn set_shipsizes(&mut self, small: usize, large: Option<usize>) -> Result<(), BattleshipError> {
         if small <= 1 {
-            return Err("Error: Smallest ship size must be greater than 1");
+            return Err(BattleshipError::SmallestShipTooSmall);
         }
     
         let large_val = large.unwrap_or(self.largestship.max(small));
     
         if large_val <= 1 || large_val < small {
-            return Err("Error: Largest ship size must be at least as large as the smallest ship size and greater than 1");
+            return Err(BattleshipError::LargestShipTooSmall);
         }
     
         self.smallestship = small;
         self.largestship = large_val;
+        self.fleet_given = true;
         Ok(())
+    }
+
+    pub fn get_fleet_given(&self) -> bool {
+        self.fleet_given
     }    
 
+    pub fn get_ai_level(&self) -> Intelligence {
+        self.ai_level
+    }
+
+    pub fn set_ai_level(&mut self, level: Intelligence) {
+        self.ai_level = level;
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Setting the seed restarts the random sequence
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.seeded = true;
+        self.rng = utils::rng_from_seed(seed);
+    }
+
+    pub fn get_seeded(&self) -> bool {
+        self.seeded
+    }
+
+    pub fn get_rng(&mut self) -> &mut GameRng {
+        &mut self.rng
+    }
+
+    // The generator without drawing from it, for saving
+    pub fn get_rng_state(&self) -> &GameRng {
+        &self.rng
+    }
+
+    // Hand the generator to code that also needs to borrow the boards, give it back with set_rng
+    pub fn take_rng(&mut self) -> GameRng {
+        std::mem::replace(&mut self.rng, utils::rng_from_seed(self.seed))
+    }
+
+    pub fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+    }
+
+    pub fn get_ai_heat_map(&self) -> &Vec<Vec<usize>> {
+        &self.ai_heat_map
+    }
+
+    pub fn set_ai_heat_map(&mut self, heat_map: Vec<Vec<usize>>) {
+        self.ai_heat_map = heat_map;
+    }
+
+    pub fn get_turn(&self) -> usize {
+        self.turn
+    }
+
+    pub fn set_turn(&mut self, turn: usize) {
+        self.turn = turn;
+    }
+
+    pub fn get_current_player(&self) -> usize {
+        self.current
+    }
+
+    pub fn set_current_player(&mut self, player: usize) {
+        self.current = player;
+    }
+
+    pub fn get_eliminated(&self) -> &[usize] {
+        &self.eliminated
+    }
+
+    pub fn set_eliminated(&mut self, eliminated: Vec<usize>) {
+        self.eliminated = eliminated;
+    }
+
+    // A player is in the game while they have ships afloat
+    pub fn is_alive(&self, player: usize) -> bool {
+        self.boards.get(player).is_some_and(|board| !board.return_ships().is_empty() && !board.all_ships_sunk())
+    }
+
+    pub fn alive_count(&self) -> usize {
+        (0..self.boards.len()).filter(|&num| self.is_alive(num)).count()
+    }
+
+    // Players with no ships at all are out before the first shot, in board order
+    pub fn eliminate_empty_boards(&mut self) {
+        for num in 0..self.boards.len() {
+            if !self.is_alive(num) && !self.eliminated.contains(&num) {
+                self.eliminated.push(num);
+            }
+        }
+        if !self.is_alive(self.current) {
+            self.current = self.next_alive(self.current);
+        }
+    }
+
+    // The next player still in the game after from, from itself if nobody else is
+    pub fn next_alive(&self, from: usize) -> usize {
+        let count = self.boards.len();
+        (1..=count)
+            .map(|step| (from + step) % count)
+            .find(|&num| self.is_alive(num))
+            .unwrap_or(from)
+    }
+
+    // Count a shot at target and pass the turn on, true if the shot put target out of the game
+    pub fn end_turn(&mut self, target: usize) -> bool {
+        let out = self.count_shot(target);
+        if self.alive_count() > 1 {
+            self.current = self.next_alive(self.current);
+        }
+        out
+    }
+
+    // Count a shot at target without passing the turn, true if the shot put target out of the game
+    fn count_shot(&mut self, target: usize) -> bool {
+        self.turn += 1;
+        let out = !self.is_alive(target) && !self.eliminated.contains(&target);
+        if out {
+            self.eliminated.push(target);
+        }
+        out
+    }
+
+    // Refuse a shot the rules do not allow shooter to take at target
+    fn check_shot(&self, shooter: usize, target: usize) -> Result<(), BattleshipError> {
+        if self.alive_count() <= 1 {
+            return Err(BattleshipError::GameOver);
+        }
+        if target == shooter {
+            return Err(BattleshipError::OwnBoard);
+        }
+        if !self.is_alive(shooter) {
+            return Err(BattleshipError::PlayerOut(shooter));
+        }
+        if !self.is_alive(target) {
+            return Err(BattleshipError::InvalidTarget(target));
+        }
+        Ok(())
+    }
+
+    // Resolve a shot at target, the board must exist
+    fn take_shot_at(&mut self, target: usize, coord: Coord) -> Result<ShotResult, BattleshipError> {
+        if !self.in_bounds(coord) {
+            return Err(BattleshipError::OutOfBounds(coord.to_string()));
+        }
+        match self.boards.get_mut(target) {
+            Some(board) => board.take_shot(coord),
+            None => Err(BattleshipError::InvalidTarget(target)),
+        }
+    }
+
+    // The current player fires at target.  Illegal shots return an error and do not use up the turn, these
+    // are the rules for every game whether a GameSession or the command line is driving it
+    pub fn fire(&mut self, target: usize, coord: Coord) -> Result<TurnReport, BattleshipError> {
+        if self.turn == 0 {
+            self.eliminate_empty_boards();                  // Nothing to shoot at, out before the start
+        }
+        let shooter = self.current;
+        self.check_shot(shooter, target)?;
+        let result = self.take_shot_at(target, coord)?;
+        let eliminated = self.end_turn(target);
+        Ok(TurnReport {
+            turn: self.turn,
+            shooter,
+            target,
+            coord,
+            result,
+            eliminated,
+        })
+    }
+
+    // A shot outside the turn order, as scripted --guess rounds and replays fire them.  A known shooter is held
+    // to the same targets as fire, without one any board can be shot at.  The shot is counted, the turn only
+    // moves on if the player who has it went out
+    pub fn fire_out_of_turn(&mut self, shooter: Option<usize>, target: usize, coord: Coord) -> Result<(ShotResult, bool), BattleshipError> {
+        if self.turn == 0 {
+            self.eliminate_empty_boards();
+        }
+        if let Some(shooter) = shooter {
+            self.check_shot(shooter, target)?;
+        }
+        let result = self.take_shot_at(target, coord)?;
+        let eliminated = self.count_shot(target);
+        if !self.is_alive(self.current) && self.alive_count() > 0 {
+            self.current = self.next_alive(self.current);
+        }
+        Ok((result, eliminated))
+    }
+
     pub fn get_col_row(&self) -> (usize,usize) {
         (self.cols, self.rows)
     }
@@ -193,19 +525,24 @@ impl GameData {
         self.player_count += 1;
     }
 
-    pub fn decrement_playercount(&mut self) -> Result<(), &str> {
-        if self.player_count <= 0 {
-            return Err("Error: Dropped below 0 players.");
+    pub fn decrement_playercount(&mut self) -> Result<(), BattleshipError> {
+        if self.player_count == 0 {
+            return Err(BattleshipError::PlayerCountUnderflow);
         }
         self.player_count -= 1;
         Ok(())
     }
 
+    pub fn get_boards(&self) -> &[PlayBoard] {
+        &self.boards
+    }
+
     pub fn get_boards_len(&self) -> usize {
         self.boards.len()
     }
 
-    pub fn boards_add(&mut self, board: PlayBoard) {
+    pub fn boards_add(&mut self, mut board: PlayBoard) {
+        board.set_dimensions(self.cols, self.rows);
         self.boards.push(board);
     }
 
@@ -227,8 +564,12 @@ impl GameData {
         self.boards.get(playernum)
     }
 
-    pub fn in_bounds(&self, col: usize, row: usize) -> bool{
-        row < self.rows && col < self.cols
+    pub fn boards_get_player_mut(&mut self, playernum: usize) -> Option<&mut PlayBoard> {
+        self.boards.get_mut(playernum)
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool{
+        coord.row < self.rows && coord.col < self.cols
     }
 
     pub fn remove_first_board(&mut self) -> Option<PlayBoard> {
@@ -240,66 +581,92 @@ impl GameData {
 
 }
 
+// Equal when both hold the same game: board size, fleet, player count, every player's ships and guesses and the turn.
+// Settings of the run (seed, filename, transcript, AI level and the mode flags) are not compared
+impl PartialEq for GameData {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && self.player_count == other.player_count
+            && self.smallestship == other.smallestship
+            && self.largestship == other.largestship
+            && self.rules == other.rules
+            && self.turn == other.turn
+            && self.current == other.current
+            && self.eliminated == other.eliminated
+            && self.boards == other.boards
+    }
+}
+
 impl Default for GameData {
     fn default() -> Self {
+        let seed: u64 = rand::random();                // Random unless --seed replaces it
         Self {
             rows: 10,
             cols: 10,
-            player_count: 1,
+            player_count: 0,
             loaded: false,
             interactive: false,
+            lenient: false,
+            warnings: Vec::new(),
             filename: "".to_string(),
+            format: FileFormat::Legacy,
+            rules: DEFAULT_RULES.to_string(),
             smallestship: 2,
             largestship: 5,
+            fleet_given: false,
+            ai_level: Intelligence::Probability,
+            ai_heat_map: Vec::new(),
+            seed,
+            seeded: false,
+            rng: utils::rng_from_seed(seed),
+            turn: 0,
+            current: 0,
+            eliminated: Vec::new(),
+            transcript: None,
             boards: Vec::new()
         }
     }
 }
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct ShipBoundingBox {
     pub ship_id: usize,
-    pub start: (usize, usize),
-    pub end: (usize, usize),
+    pub start: Coord,
+    pub end: Coord,
+    hits: Vec<bool>,                                // One entry per segment from start to end
 }
 
 // Definition of ships and their locations
-// Points are stored in column, row
 impl ShipBoundingBox {
 
     pub fn new(
         ship_id: usize,
-        start: (usize, usize),                              // Row, Col
+        start: Coord,
         direction: Direction,
         board: &GameData,
         player: &PlayBoard,
-    ) -> Option<ShipBoundingBox> {
-        let mut tmp_end: (usize, usize) = (0,0);
-        if direction == Direction::Vertical                  // Vertical ship
-        {
-            tmp_end = (start.1 + ship_id - 1, start.0);
-        } else {                                            // Horizontal ship
-            tmp_end = (start.1, start.0 + ship_id - 1);
-        }
-        if !board.in_bounds(start.0, start.1) || !board.in_bounds(tmp_end.0, tmp_end.1) {         // Valid for placement
-            return None;
+    ) -> Result<ShipBoundingBox, PlacementError> {
+        if ship_id == 0 {
+            return Err(PlacementError::ZeroSize);
         }
-        if start.0 != tmp_end.0 && start.1 != tmp_end.1 {               // Check for diagonal
-            return None;
+        let tmp_end = start.step(direction, ship_id - 1);
+        if let Some(cell) = start.line(direction, ship_id).find(|&point| !board.in_bounds(point)) {     // Valid for placement
+            return Err(PlacementError::OffBoard { ship_id, start, direction, cell });
         }
         // Check for collision and ship_id duplicate
         if player.player_has_ship_id(ship_id) {             // Ship is a duplicate
-            return None;
+            return Err(PlacementError::DuplicateId { ship_id });
+        }
+        if let Some((other, cell)) = player.find_collision(start, ship_id, direction) {     // Check for collision and overlap
+            return Err(PlacementError::Overlap { ship_id, other, cell });
         }
-        if player.ships.iter().any(|existing_ship| {                // Check for collision and overlap
-            ShipBoundingBox::overlaps(&ShipBoundingBox{ship_id, start, end: tmp_end}, existing_ship) 
-        }) {
-            return None;
-        } 
         
-        Some(ShipBoundingBox {
-            ship_id: ship_id,
-            start: (start.0, start.1),
-            end: (tmp_end.0, tmp_end.1),
+        Ok(ShipBoundingBox {
+            ship_id,
+            start,
+            end: tmp_end,
+            hits: vec![false; ship_id],
             })
     }
 
@@ -307,59 +674,68 @@ impl ShipBoundingBox {
         self.ship_id
     }
 
+    // A single cell ship counts as horizontal
+    pub fn get_direction(&self) -> Direction {
+        if self.start.col == self.end.col && self.start.row != self.end.row {
+            Direction::Vertical
+        } else {
+            Direction::Horizontal
+        }
+    }
+
     pub fn overlap_possible(
         &self, 
         ship2_size: usize, 
-        ship2_point: (usize, usize), 
+        ship2_point: Coord,
         ship2_dir: &Direction
     ) -> bool {
-        let self_horizontal = self.start.1 == self.end.1;
+        let self_horizontal = self.start.row == self.end.row;
         let ship2_horizontal = *ship2_dir == Direction::Horizontal;
     
         if self_horizontal && ship2_horizontal {
             // Both ships are horizontal → check if they are in the same row and their columns overlap
-            return self.start.1 == ship2_point.1 // Same row
-                && self.start.0 <= ship2_point.0 + ship2_size - 1 
-                && ship2_point.0 <= self.end.0;
+            return self.start.row == ship2_point.row // Same row
+                && self.start.col < ship2_point.col + ship2_size
+                && ship2_point.col <= self.end.col;
         }
     
         if !self_horizontal && !ship2_horizontal {
             // Both ships are vertical → check if they are in the same column and their rows overlap
-            return self.start.0 == ship2_point.0 // Same column
-                && self.start.1 <= ship2_point.1 + ship2_size - 1
-                && ship2_point.1 <= self.end.1;
+            return self.start.col == ship2_point.col // Same column
+                && self.start.row < ship2_point.row + ship2_size
+                && ship2_point.row <= self.end.row;
         }
     
         // At this point, one ship is vertical, and one is horizontal
         let (horiz_start, horiz_len, vert_start, vert_len) = if self_horizontal {
-            (self.start, self.end.0 - self.start.0 + 1, ship2_point, ship2_size)
+            (self.start, self.end.col - self.start.col + 1, ship2_point, ship2_size)
         } else {
-            (ship2_point, ship2_size, self.start, self.end.1 - self.start.1 + 1)
+            (ship2_point, ship2_size, self.start, self.end.row - self.start.row + 1)
         };
     
         // The horizontal ship must pass through the vertical ship's column
         // AND the vertical ship must pass through the horizontal ship's row
-        horiz_start.0 <= vert_start.0 && vert_start.0 <= horiz_start.0 + horiz_len &&
-        vert_start.1 <= horiz_start.1 && horiz_start.1 <= vert_start.1 + vert_len - 1
+        horiz_start.col <= vert_start.col && vert_start.col < horiz_start.col + horiz_len &&
+        vert_start.row <= horiz_start.row && horiz_start.row < vert_start.row + vert_len
     }
     
     // Check for a collision between ships
     pub fn overlaps(&self, ship2: &ShipBoundingBox) -> bool {
-        let self_horizontal = self.start.1 == self.end.1;
-        let ship2_horizontal = ship2.start.1 == ship2.end.1;
+        let self_horizontal = self.start.row == self.end.row;
+        let ship2_horizontal = ship2.start.row == ship2.end.row;
 
         if self_horizontal && ship2_horizontal {
             // Both ships are horizontal → check if they are in the same row and their columns overlap
-            return self.start.1 == ship2.start.1 // Same row
-                && self.start.0 <= ship2.end.0 
-                && ship2.start.0 <= self.end.0; // Overlapping column range
+            return self.start.row == ship2.start.row // Same row
+                && self.start.col <= ship2.end.col 
+                && ship2.start.col <= self.end.col; // Overlapping column range
         }
 
         if !self_horizontal && !ship2_horizontal {
             // Both ships are vertical → check if they are in the same column and their rows overlap
-            return self.start.0 == ship2.start.0 // Same column
-                && self.start.1 <= ship2.end.1 
-                && ship2.start.1 <= self.end.1; // Overlapping row range
+            return self.start.col == ship2.start.col // Same column
+                && self.start.row <= ship2.end.row 
+                && ship2.start.row <= self.end.row; // Overlapping row range
         }
 
         // At this point, one ship is vertical, and one is horizontal
@@ -371,45 +747,83 @@ impl ShipBoundingBox {
 
         // The horizontal ship must pass through the vertical ship's column
         // AND the vertical ship must pass through the horizontal ship's row
-        horiz_ship.start.0 <= vert_ship.start.0 && vert_ship.start.0 <= horiz_ship.end.0 &&
-        vert_ship.start.1 <= horiz_ship.start.1 && horiz_ship.start.1 <= vert_ship.end.1
+        horiz_ship.start.col <= vert_ship.start.col && vert_ship.start.col <= horiz_ship.end.col &&
+        vert_ship.start.row <= horiz_ship.start.row && horiz_ship.start.row <= vert_ship.end.row
     }
 
 
 
-    pub fn points_collision(&self, other_start: (usize, usize), other_end: (usize, usize)) -> bool {
-        (self.start.1 <= other_start.1 && self.end.1 >= other_start.1) &&
-        (self.start.0 <= other_end.0 && self.end.0 >= other_start.0)
+    pub fn points_collision(&self, other_start: Coord, other_end: Coord) -> bool {
+        (self.start.row <= other_start.row && self.end.row >= other_start.row) &&
+        (self.start.col <= other_end.col && self.end.col >= other_start.col)
     }
 
-    pub fn point_in_ship(&self, row: usize, col: usize) -> bool {
-        if self.start.0 == self.end.0 { // Vertical ship
-            (col == self.start.0) && (row >= self.start.1) && (row <= self.end.1) //Check col and row
-        } else { // Horizontal ship
-            (row == self.start.1) && (col >= self.start.0) && (col <= self.end.0) //Check row and col
+    // Mark the segment at point as hit, returns False if the point is not part of the ship
+    pub fn register_hit(&mut self, point: Coord) -> bool {
+        if !self.point_in_ship(point) {
+            return false;
         }
+        let segment = (point.col - self.start.col) + (point.row - self.start.row);     // Only one of these moves
+        self.hits[segment] = true;
+        true
+    }
+
+    // Hit mask running from start to end
+    pub fn hits(&self) -> &[bool] {
+        &self.hits
+    }
+
+    pub fn hit_count(&self) -> usize {
+        self.hits.iter().filter(|&&hit| hit).count()
+    }
+
+    pub fn length(&self) -> usize {
+        self.hits.len()
+    }
+
+    // Segments that have not been hit yet
+    pub fn remaining(&self) -> usize {
+        self.length() - self.hit_count()
+    }
+
+    pub fn is_sunk(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    // Every point covered by the ship from start to end
+    pub fn get_points(&self) -> Vec<Coord> {
+        self.start.line(self.get_direction(), self.length()).collect()
+    }
+
+    pub fn point_in_ship(&self, point: Coord) -> bool {
+        (self.start.row..=self.end.row).contains(&point.row) && (self.start.col..=self.end.col).contains(&point.col)
     }
 }
 
-// Return an option for 2d matrix with 0's for water and nums for ships
-pub fn create_my_board_from_player(myboard: &GameData, player: &mut PlayBoard) -> Vec<Vec<usize>> {
+// Return a 2d matrix with 0's for water and nums for ships, the player is left as it was
+pub fn create_my_board_from_player(myboard: &GameData, player: &PlayBoard) -> Vec<Vec<usize>> {
     let (my_cols, my_rows) = myboard.get_col_row();
     let mut tmpboard = vec![vec![0; my_cols]; my_rows];  // Create 0-initialized board
 
-    while let Some(this_ship) = player.remove_first_ship() {
-        let (x1, y1) = this_ship.start;
-        let (x2, y2) = this_ship.end;
-
-        if y1 == y2 {  // Ship is horizontal
-            for x in x1..=x2 {
-                tmpboard[y1][x] = this_ship.ship_id;
-            }
-        } else {  // Ship is vertical
-            for y in y1..=y2 {
-                tmpboard[y][x1] = this_ship.ship_id;
-            }
+    for this_ship in player.return_ships() {
+        for point in this_ship.get_points() {
+            tmpboard[point.row][point.col] = this_ship.ship_id;
         }
     }
 
     tmpboard
 }
+
+// The board as a shooter sees it after the game: ship ids where nothing has hit yet, X for hits, * for misses
+pub fn create_shot_board_from_player(myboard: &GameData, player: &PlayBoard) -> Vec<Vec<String>> {
+    let mut tmpboard: Vec<Vec<String>> = create_my_board_from_player(myboard, player).iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+    for coord in player.get_hits().iter() {
+        tmpboard[coord.row][coord.col] = "X".to_string();
+    }
+    for coord in player.get_misses().iter() {
+        tmpboard[coord.row][coord.col] = "*".to_string();
+    }
+    tmpboard
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::code::enums::Direction;
+use crate::code::error::BattleshipError;
+use crate::code::utils;
+
+// Column letters longer than this are past any board and would overflow base_26
+const MAX_COLUMN_LETTERS: usize = 8;
+
+// A cell on a board, both 0 based.  Written as the column letters then the 1 based row, e.g. A1 or AA10
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    // The cell steps cells further along direction
+    pub fn step(&self, direction: Direction, steps: usize) -> Coord {
+        match direction {
+            Direction::Horizontal => Coord { row: self.row, col: self.col + steps },
+            Direction::Vertical => Coord { row: self.row + steps, col: self.col },
+        }
+    }
+
+    // Every cell of a line length long starting here
+    pub fn line(self, direction: Direction, length: usize) -> impl Iterator<Item = Coord> {
+        (0..length).map(move |steps| self.step(direction, steps))
+    }
+}
+
+impl FromStr for Coord {
+    type Err = BattleshipError;
+
+    // Letters then digits, case and whitespace are ignored.  Row 0 is above every board so it is out of bounds
+    fn from_str(mybuf: &str) -> Result<Self, Self::Err> {
+        let upper: String = mybuf.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+        let letters: String = upper.chars().take_while(|c| c.is_ascii_uppercase()).collect();
+        let digits = &upper[letters.len()..];
+
+        if letters.is_empty() || digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(BattleshipError::InvalidFormat(mybuf.to_string()));
+        }
+        if letters.len() > MAX_COLUMN_LETTERS {
+            return Err(BattleshipError::InvalidColumn(mybuf.to_string()));
+        }
+        let row = digits.parse::<usize>().map_err(|_| BattleshipError::InvalidRow(mybuf.to_string()))?;
+        if row == 0 {
+            return Err(BattleshipError::OutOfBounds(mybuf.to_string()));
+        }
+        Ok(Coord {
+            row: row - 1,
+            col: utils::base_26(letters),
+        })
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", utils::base26_to_letter(self.col), self.row + 1)
+    }
+}
@@ -1,59 +1,58 @@
-
+use std::io::{BufRead, Write};
 use crate::code::board::GameData;
+use crate::code::coord::Coord;
+use crate::code::error::BattleshipError;
 
 // Query the array to see what is at each location.  This is mainly for the challenge
-pub fn query_array(mybuf: &str, myboard: &mut GameData) -> Result<i16, String> {
-    let mut row_str = String::new();
-    let mut col_str = String::new();
-    if myboard.get_loaded() == false {
-        return Err("Error: You have not loaded a file yet!".to_string());
+// Accepts <player>,<coord> or just <coord> for the default player 1.  Returns the ship id or 0 for water
+pub fn query_array(mybuf: &str, myboard: &GameData) -> Result<usize, BattleshipError> {
+    if !myboard.get_loaded() {
+        return Err(BattleshipError::NotLoaded);
     }
+    resolve_query(mybuf, myboard)
+}
 
-    for c in mybuf.chars() {
-        if c.is_ascii_uppercase() {
-            row_str.push(c);
-        } else if c.is_ascii_digit() {
-            col_str.push(c);
-        } else if !c.is_whitespace() {
-            return Err("Invalid format in query.".to_string());
-        }
-    }
-    if row_str.is_empty() || col_str.is_empty() {
-        return Err(format!("Invalid query (missing row or column): {}", mybuf).to_string());
+// Look up a single coordinate on one player (1 based)
+pub fn query_player(myboard: &GameData, playernum: usize, coord: &str) -> Result<usize, BattleshipError> {
+    let player = match playernum.checked_sub(1).and_then(|num| myboard.boards_get_player(num)) {
+        Some(player) => player,
+        None => return Err(BattleshipError::InvalidPlayer(playernum.to_string())),
+    };
+    let coord: Coord = coord.parse()?;
+    if !myboard.in_bounds(coord) {
+        return Err(BattleshipError::OutOfBounds(coord.to_string()));
     }
+    Ok(player.handle_shot(coord).unwrap_or(0))
+}
 
-    let mut row_index: usize = 0;
-    for c in row_str.chars() {
-        row_index = row_index * 26 + (c as u8 - b'A' + 1) as usize;
-    }
-    row_index -= 1;                         // A is row 0
+// Split <player>,<coord> and resolve it, without checking a file was loaded
+pub fn resolve_query(mybuf: &str, myboard: &GameData) -> Result<usize, BattleshipError> {
+    let (player_str, coord) = match mybuf.trim().split_once(',') {
+        Some((player, coord)) => (player.trim(), coord),
+        None => ("1", mybuf),
+    };
+    let playernum = player_str.parse::<usize>().map_err(|_| BattleshipError::InvalidPlayer(mybuf.to_string()))?;
+    query_player(myboard, playernum, coord)
+}
 
-    let col_index = match col_str.parse::<usize>() {
-        Ok(n) => n - 1,
-        Err(_) => {
-            return Err("Invalid column number".to_string());
+// Answer every query in reader, one result per line in the same order.  Blank lines are skipped.
+// Out of bounds answers OOB, malformed lines are reported with their line number.  Returns the count of bad lines
+pub fn answer_queries<R: BufRead, W: Write>(reader: R, writer: &mut W, myboard: &GameData) -> Result<usize, BattleshipError> {
+    let mut bad_lines = 0;
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
         }
-    };
-/*Deprecated since now no longer storing rows 
-    let (real_row, real_col) = myboard.get_row_col();
-    if row_index <=  real_row as usize && col_index <= real_col as usize{
-        match myboard.boards_get_player(playerid as usize) {
-            Some(value) => {
-                match value.get_board_loc(row_index, col_index) {
-                    Some(val) => Ok(val),
-                    None => Err(format!("Error: Unable to find data at {}", mybuf).to_string())
-                }
-            },
-            None => {
-                Err(format!("Error: No data at point {}, {} in player {}, correlating to {}",
-                     row_index, col_index, playerid, mybuf).to_string())
+        match resolve_query(&line, myboard) {
+            Ok(value) => writeln!(writer, "{}", value)?,
+            Err(err) if err.is_out_of_bounds() => writeln!(writer, "OOB")?,
+            Err(err) => {
+                bad_lines += 1;
+                writeln!(writer, "ERROR line {}: {}", line_num + 1, err)?;
             }
         }
     }
-    else {
-        Err(format!("Query out of bounds: Row {}, Column {}", row_index + 1, col_index + 1).to_string())
-    }
-    */
-    // Fix this, but I think query_array is completely done, likely going to only return hit/miss
-    Ok(1)
-}
\ No newline at end of file
+    writer.flush()?;
+    Ok(bad_lines)
+}
@@ -1,22 +1,8 @@
-use::std;
+use serde::{Deserialize, Serialize};
 
-// Error output for parse to int from string
-pub const MAX_SIZE: usize = std::i16::MAX as usize;
-pub const MIN_SIZE: usize = std::i16::MIN as usize;
-
-pub enum RowColErr {
-    Failed,
-    TooSmall,
-    TooBig,
-}
-
-// Specific enum to give individual instances as errors.
-pub enum QueryError {
-    InvalidFormat,
-    InvalidRow,
-    InvalidColumn,
-    OutOfBounds,
-}
+// Range parse_to_usize accepts for board sizes, counts and ship ids
+pub const MAX_SIZE: usize = i16::MAX as usize;
+pub const MIN_SIZE: usize = 1;
 
 // Enum to declare state of create
 #[derive(PartialEq)]
@@ -30,15 +16,106 @@ pub enum StateCreate {
     StateFileName,
     StateCreate,
 }
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Direction {
     Horizontal,
     Vertical,
 }
 
-#[derive(PartialEq, Clone, Copy, Debug)]
-
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HitMiss {
     Hit,
     Miss,
 }
+
+// Result of a single shot, ship ids are carried on hits
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ShotResult {
+    Miss,
+    Hit(usize),
+    Sunk(usize),
+}
+
+// AI difficulty tiers, AiBoard::intelligence is the index into this ladder
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Intelligence {
+    Random,                                         // 0 Shoots any untried cell
+    HuntTarget,                                     // 1 Random until a hit, then works the neighbours
+    ParityTarget,                                   // 2 Hunts on a checkerboard of the smallest ship left
+    Probability,                                    // 3 Full placement density heat map
+    MonteCarlo,                                     // 4 Samples whole fleets consistent with the shots
+}
+
+impl Intelligence {
+    pub const ALL: [Intelligence; 5] = [
+        Intelligence::Random,
+        Intelligence::HuntTarget,
+        Intelligence::ParityTarget,
+        Intelligence::Probability,
+        Intelligence::MonteCarlo,
+    ];
+
+    // Anything above the top tier plays as the top tier
+    pub fn from_level(level: usize) -> Intelligence {
+        Intelligence::ALL[level.min(Intelligence::ALL.len() - 1)]
+    }
+
+    pub fn get_level(&self) -> usize {
+        Intelligence::ALL.iter().position(|x| x == self).unwrap_or(0)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Intelligence::Random => "random",
+            Intelligence::HuntTarget => "hunt",
+            Intelligence::ParityTarget => "parity",
+            Intelligence::Probability => "probability",
+            Intelligence::MonteCarlo => "montecarlo",
+        }
+    }
+
+    // Accepts the level number, the tier name or the difficulty name
+    pub fn parse(buf: &str) -> Option<Intelligence> {
+        if let Ok(level) = buf.trim().parse::<usize>() {
+            return Intelligence::ALL.get(level).copied();
+        }
+        match buf.trim().to_lowercase().as_str() {
+            "random" | "novice" => Some(Intelligence::Random),
+            "hunt" | "easy" => Some(Intelligence::HuntTarget),
+            "parity" | "medium" => Some(Intelligence::ParityTarget),
+            "probability" | "hard" => Some(Intelligence::Probability),
+            "montecarlo" | "expert" => Some(Intelligence::MonteCarlo),
+            _ => None,
+        }
+    }
+}
+
+// Layout of a board file on disk, load_file works out which one it is reading
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum FileFormat {
+    #[default]
+    Legacy,                                         // Rows, columns and player count as three bare numbers
+    V2,                                             // Magic and version line then key=value headers
+    Json,                                           // The whole game as one JSON document, see json.rs
+}
+
+impl FileFormat {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            FileFormat::Legacy => "legacy",
+            FileFormat::V2 => "v2",
+            FileFormat::Json => "json",
+        }
+    }
+
+    pub fn parse(buf: &str) -> Option<FileFormat> {
+        match buf.trim().to_lowercase().as_str() {
+            "legacy" | "v1" | "1" => Some(FileFormat::Legacy),
+            "v2" | "2" => Some(FileFormat::V2),
+            "json" => Some(FileFormat::Json),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use crate::code::coord::Coord;
+use crate::code::enums;
+use crate::code::enums::Direction;
+
+// Every error the crate can return.  Display keeps the wording the command line has always printed
+#[derive(Debug)]
+pub enum BattleshipError {
+    Io {                                            // Opening, reading or writing a file
+        context: String,
+        source: io::Error,
+    },
+    Parse {                                         // A board file that is not laid out as expected, 1 based position
+        line: usize,
+        column: Option<usize>,
+        message: String,
+        source: Option<Box<BattleshipError>>,
+    },
+    EmptyFilename,
+    NotLoaded,
+    Schema(String),                                 // JSON that parses but does not describe a valid game
+
+    // Numbers given for sizes and counts
+    NotANumber(String),
+    TooSmall,
+    TooBig,
+    SmallestShipTooSmall,
+    LargestShipTooSmall,
+    PlayerCountUnderflow,
+
+    // Coordinates and players in queries and shots, holding what was entered
+    InvalidFormat(String),
+    InvalidRow(String),
+    InvalidColumn(String),
+    OutOfBounds(String),
+    InvalidPlayer(String),
+    AlreadyGuessed(Coord),
+
+    // Ships and fleets
+    Placement(PlacementError),
+    FleetTooLarge { small: usize, large: usize, cols: usize, rows: usize },
+    NoArrangement { small: usize, large: usize, cols: usize, rows: usize },
+
+    // Games between players or strategies
+    NotEnoughPlayers,
+    NotEnoughStrategies,
+    GameOver,
+    OwnBoard,
+    PlayerOut(usize),                               // 0 based player index
+    InvalidTarget(usize),                           // 0 based player index
+    NoShot(String),                                 // Name of the strategy that gave up
+    IncompleteFleet(String),                        // Name of the strategy that placed it
+}
+
+impl BattleshipError {
+    pub fn io(context: &str, source: io::Error) -> Self {
+        BattleshipError::Io { context: context.to_string(), source }
+    }
+
+    // A parse error at a 1 based line and column of a file
+    pub fn parse(line: usize, column: Option<usize>, message: String) -> Self {
+        BattleshipError::Parse { line, column, message, source: None }
+    }
+
+    // Place an error found while reading a file at the line and column it came from
+    pub fn at(self, line: usize, column: Option<usize>, message: String) -> Self {
+        BattleshipError::Parse { line, column, message, source: Some(Box::new(self)) }
+    }
+
+    // True for errors a query answers with OOB rather than rejecting the line
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(self, BattleshipError::OutOfBounds(_))
+    }
+}
+
+impl fmt::Display for BattleshipError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BattleshipError::Io { context, source } => write!(f, "{}: {}", context, source),
+            BattleshipError::Parse { line, column, message, .. } => {
+                write!(f, "{} (line {}", message, line)?;
+                if let Some(column) = column {
+                    write!(f, ", column {}", column)?;
+                }
+                write!(f, ")")
+            }
+            BattleshipError::EmptyFilename => write!(f, "Error: Filename cannot be empty."),
+            BattleshipError::NotLoaded => write!(f, "Error: You have not loaded a file yet!"),
+            BattleshipError::Schema(message) => write!(f, "{}", message),
+            BattleshipError::NotANumber(value) => write!(f, "Error: Failed to parse value: {}", value),
+            BattleshipError::TooSmall => write!(f, "Error: Value must be greater than or equal to 1"),
+            BattleshipError::TooBig => write!(f, "Error: Value must be less than {}", enums::MAX_SIZE),
+            BattleshipError::SmallestShipTooSmall => write!(f, "Error: Smallest ship size must be greater than 1"),
+            BattleshipError::LargestShipTooSmall => write!(f, "Error: Largest ship size must be at least as large as the smallest ship size and greater than 1"),
+            BattleshipError::PlayerCountUnderflow => write!(f, "Error: Dropped below 0 players."),
+            BattleshipError::InvalidFormat(query) => write!(f, "Invalid query (missing row or column): {}", query.trim()),
+            BattleshipError::InvalidRow(query) => write!(f, "Invalid row number: {}", query.trim()),
+            BattleshipError::InvalidColumn(query) => write!(f, "Invalid column: {}", query.trim()),
+            BattleshipError::OutOfBounds(query) => write!(f, "OUT-OF-BOUNDS: {}", query.trim()),
+            BattleshipError::InvalidPlayer(query) => write!(f, "Invalid playerid: {}", query.trim()),
+            BattleshipError::AlreadyGuessed(coord) => write!(f, "Error: {} has already been guessed.", coord),
+            BattleshipError::Placement(err) => write!(f, "{}", err),
+            BattleshipError::FleetTooLarge { small, large, cols, rows } => {
+                write!(f, "Error: The fleet {}..={} cannot fit on a {}x{} board.", small, large, cols, rows)
+            }
+            BattleshipError::NoArrangement { small, large, cols, rows } => {
+                write!(f, "Error: No arrangement of the fleet {}..={} fits on a {}x{} board.", small, large, cols, rows)
+            }
+            BattleshipError::NotEnoughPlayers => write!(f, "Error: A game needs at least 2 players."),
+            BattleshipError::NotEnoughStrategies => write!(f, "Error: The arena needs at least 2 strategies."),
+            BattleshipError::GameOver => write!(f, "Error: The game is already over."),
+            BattleshipError::OwnBoard => write!(f, "Error: A player cannot target their own board."),
+            BattleshipError::PlayerOut(player) => write!(f, "Error: Player {} is out of the game and cannot shoot.", player + 1),
+            BattleshipError::InvalidTarget(player) => write!(f, "Error: Player {} is not a valid target.", player + 1),
+            BattleshipError::NoShot(name) => write!(f, "Error: {} found no shot to take.", name),
+            BattleshipError::IncompleteFleet(name) => write!(f, "Error: {} did not place the whole fleet.", name),
+        }
+    }
+}
+
+impl Error for BattleshipError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BattleshipError::Io { source, .. } => Some(source),
+            BattleshipError::Parse { source: Some(source), .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<PlacementError> for BattleshipError {
+    fn from(err: PlacementError) -> Self {
+        BattleshipError::Placement(err)
+    }
+}
+
+impl From<io::Error> for BattleshipError {
+    fn from(err: io::Error) -> Self {
+        BattleshipError::io("Error: I/O failure", err)
+    }
+}
+
+// The rule a ship broke when ShipBoundingBox::new turned it down
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PlacementError {
+    ZeroSize,
+    OffBoard {                                      // cell is the first part of the ship past the edge
+        ship_id: usize,
+        start: Coord,
+        direction: Direction,
+        cell: Coord,
+    },
+    DuplicateId {
+        ship_id: usize,
+    },
+    Overlap {                                       // cell is the first part shared with the other ship
+        ship_id: usize,
+        other: usize,
+        cell: Coord,
+    },
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::ZeroSize => write!(f, "Error: A ship must be at least 1 long."),
+            PlacementError::OffBoard { ship_id, start, direction, cell } => {
+                let heading = if *direction == Direction::Horizontal { "across" } else { "down" };
+                write!(f, "Error: Ship {} at {} going {} runs off the board at {}.", ship_id, start, heading, cell)
+            }
+            PlacementError::DuplicateId { ship_id } => write!(f, "Error: Ship {} has already been placed.", ship_id),
+            PlacementError::Overlap { ship_id, other, cell } => {
+                write!(f, "Error: Ship {} overlaps ship {} at {}.", ship_id, other, cell)
+            }
+        }
+    }
+}
+
+impl Error for PlacementError {}
@@ -1,197 +1,202 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use crate::code::board::GameData;
-use crate::code::utils::{output_string, parse_to_usize, handle_file_error};
-use crate::code::enums::RowColErr;
+use crate::code::coord::Coord;
+use crate::code::utils;
+use crate::code::utils::parse_to_usize;
+use crate::code::error::BattleshipError;
+use crate::code::json;
 use crate::code::board::PlayBoard;
-use crate::code::enums::Direction;
+use crate::code::enums;
+use crate::code::enums::{Direction, FileFormat};
 use std::collections::{HashSet, HashMap};
-use std::error::Error;
 use crate::code::board::ShipBoundingBox;
-use std::fmt;
 use crate::code::board;
 
-#[derive(Debug)]
-struct LoadError {
-    message: String,
-}
-
-impl fmt::Display for LoadError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
+// Header lines at the top of a legacy board file: rows, columns, player count
+const HEADER_LINES: usize = 3;
 
-impl Error for LoadError {} // Implement the Error trait
+// A v2 file opens with the magic word and version, e.g. "BATTLESHIP v2", then key=value headers up to V2_HEADER_END
+pub const V2_MAGIC: &str = "BATTLESHIP";
+pub const V2_VERSION: usize = 2;
+pub const V2_HEADER_END: &str = "---";
 
-pub fn load_file_game_data(line: &str, myboard: &mut GameData, line_num: usize) -> Result<(), String> {
-    let tmp_line = line;
+// Parse one header line, line_num is 0 based within the header
+pub fn load_file_game_data(line: &str, myboard: &mut GameData, line_num: usize) -> Result<(), BattleshipError> {
     if line.is_empty() {
-        return Err(format!("Error: Empty line at {}", line_num));
+        return Err(BattleshipError::parse(line_num + 1, None, "Error: Empty line".to_string()));
+    }
+    if line_num >= HEADER_LINES {
+        return Err(BattleshipError::parse(line_num + 1, None, "Went too far, not sure why.".to_string()));
     }
-    // Rows handling
+    let num = parse_to_usize(line.trim()).map_err(|err| {
+        let fail_str = match err {
+            BattleshipError::TooSmall => "Error: Failed to load player correctly, too small.",
+            BattleshipError::TooBig => "Error: Failed to load player correctly, too big.",
+            _ => "Error: Failed to load player correctly, failed.",
+        };
+        err.at(line_num + 1, Some(1), fail_str.to_string())
+    })?;
     match line_num {
-        0 => {
-            match parse_to_usize(tmp_line.trim()) {
-                Ok(num) => {
-                    myboard.set_row_or_col(num, true);
-                    return Ok(());
-                }
-                Err(err) => {
-                    let mut fail_str = "".to_string();
-                    match err {
-                        RowColErr::TooSmall => {
-                            fail_str = "Error: Failed to load player correctly, too small.".to_string();
-                        }
-                        RowColErr::TooBig => {
-                            fail_str = "Error: Failed to load player correctly, too big.".to_string();
-                        }
-                        RowColErr::Failed => {fail_str = "Error: Failed to load player correctly, failed.".to_string();
-                        }   
-                    }
-                    return Err(fail_str);
-                }
-            }
-        }
-        // Columns parsing
-        1 => {
-            match parse_to_usize(tmp_line.trim()) {
-                Ok(num) => {
-                    myboard.set_row_or_col(num, false);
-                    return Ok(());
-                }
-                Err(err) => {
-                    let mut fail_str = "".to_string();
-                    match err {
-                        RowColErr::TooSmall => {
-                            fail_str = "Error: Failed to load player correctly, too small.".to_string();
-                        }
-                        RowColErr::TooBig => {
-                            fail_str = "Error: Failed to load player correctly, too big.".to_string();
-                        }
-                        RowColErr::Failed => {fail_str = "Error: Failed to load player correctly, failed.".to_string();
-                        }   
-                    }
-                    return Err(fail_str);
-                }
-            }
-        }
-        // Handle player count
-        2 => {
-            match parse_to_usize(tmp_line.trim()) {
-                Ok(num) => {
-                    myboard.set_playercount(num);
-                    return Ok(());
-                }
-                Err(err) => {
-                    let mut fail_str = "".to_string();
-                    match err {
-                        RowColErr::TooSmall => {
-                            fail_str = "Error: Failed to load player correctly, too small.".to_string();
-                        }
-                        RowColErr::TooBig => {
-                            fail_str = "Error: Failed to load player correctly, too big.".to_string();
-                        }
-                        RowColErr::Failed => {fail_str = "Error: Failed to load player correctly, failed.".to_string();
-                        }   
-                    }
-                    return Err(fail_str);
-                }
-            }
-        }
-        _ => {
-            return Err("Went too far, not sure why.".to_string())
-        }
+        0 => myboard.set_row_or_col(num, true),             // Rows
+        1 => myboard.set_row_or_col(num, false),            // Columns
+        _ => myboard.set_playercount(num),                  // Player count
     }
+    Ok(())
+}
+
+// How a player block is named in errors, 1 based to match the file
+fn player_label(player_num: usize, player_name: &str) -> String {
+    format!("player {} ({})", player_num + 1, player_name)
 }
 
-// Pass the player data in as a whole, so iterate through.
+// Pass the player data in as a whole, so iterate through.  first_line is the 1 based file line of the first player
+// and count_line the line holding the player count.
+// The players found must match the count in the header; in lenient mode a mismatch or a truncated last player is
+// only a warning on myboard and the count is set to what was loaded
 pub fn load_player_game_data<R: BufRead>(
     lines: &mut std::io::Lines<R>,
     myboard: &mut GameData,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let (play_row, play_col) = myboard.get_col_row();
+    first_line: usize,
+    count_line: usize,
+) -> Result<(), BattleshipError> {
+    let (play_col, play_row) = myboard.get_col_row();
+    let declared = myboard.get_playercount();
+    let lenient = myboard.get_lenient();
     let mut player_num = 0;
+    let mut line_num = first_line;
+    let mut blank_line: Option<usize> = None;       // Blank lines are only allowed at the end of the file
 
-    while let Some(Ok(player_name_line)) = lines.next() {
+    while let Some(player_name_line) = lines.next() {
+        let player_name_line = player_name_line.map_err(|err| {
+            BattleshipError::io(&format!("Error reading line {}", line_num), err)
+        })?;
         let player_name = player_name_line.trim();
 
-        if player_name.contains(',') || player_name.is_empty() {
-            return Err(Box::new(LoadError {
-                message: "Error: Inappropriate or blank player name.".to_string(),
-            }));
+        if player_name.is_empty() {
+            blank_line.get_or_insert(line_num);
+            line_num += 1;
+            continue;
+        }
+        if let Some(blank) = blank_line {
+            return Err(BattleshipError::parse(blank, Some(1), "Error: Inappropriate or blank player name.".to_string()));
+        }
+        if player_name.contains(',') {
+            return Err(BattleshipError::parse(
+                line_num,
+                Some(1),
+                format!("Error: Expected the name of player {} but found a board row.", player_num + 1),
+            ));
+        }
+        if player_num == declared {
+            let message = format!("Found more players than the {} declared in the header.", declared);
+            if !lenient {
+                return Err(BattleshipError::parse(line_num, Some(1), format!("Error: {}", message)));
+            }
+            myboard.add_warning(BattleshipError::parse(line_num, Some(1), message));
         }
 
         let mut player = PlayBoard::default();
         player.set_playername(player_name.to_string());
         player.set_playernum(player_num);
-        player_num += 1;
+        let label = player_label(player_num, player_name);
+        let name_line = line_num;
+        line_num += 1;
+        let first_row_line = line_num;
 
-        let mut tmp_ships_hash: HashMap<usize, HashSet<(usize, usize)>> = HashMap::new();
+        let mut tmp_ships_hash: HashMap<usize, HashSet<Coord>> = HashMap::new();
         let mut current_row: Vec<usize> = Vec::new();
-        let mut row_index = 0;
-
-        for _ in 0..play_row {
-            if let Some(Ok(line)) = lines.next() {
-                current_row.clear();
-                let row_str = line.split(',');
-                for s in row_str {
-                    let val = s.trim().parse().unwrap_or(0);
-                    current_row.push(val);
-                }
+        let mut truncated = false;
 
-                if current_row.len() > play_col {
-                    return Err(Box::new(LoadError {
-                        message: format!(
-                            "Error: Too many columns in row {} of player {}",
-                            row_index + 1,
-                            player_num - 1
-                        ),
-                    }));
-                }
-
-                for (col_index, &cell) in current_row.iter().enumerate() {
-                    if cell != 0 {
-                        tmp_ships_hash
-                            .entry(cell)
-                            .or_insert_with(HashSet::new)
-                            .insert((row_index, col_index));
+        for row_index in 0..play_row {
+            let line = match lines.next() {
+                Some(line) => line.map_err(|err| BattleshipError::io(&format!("Error reading line {}", line_num), err))?,
+                None => {
+                    let message = format!("The file ends inside {}, {} of {} rows.", label, row_index, play_row);
+                    if !lenient {
+                        return Err(BattleshipError::parse(name_line, Some(1), format!("Error: {}", message)));
                     }
+                    myboard.add_warning(BattleshipError::parse(name_line, Some(1), message));
+                    truncated = true;
+                    break;
                 }
+            };
+            current_row.clear();
+            let row_str = line.split(',');
+            for s in row_str {
+                let val = s.trim().parse().unwrap_or(0);
+                current_row.push(val);
+            }
+
+            if current_row.len() > play_col {
+                return Err(BattleshipError::parse(
+                    line_num,
+                    Some(utils::field_column(&line, play_col)),
+                    format!("Error: Too many columns in row {} of {}", row_index + 1, label),
+                ));
+            }
+            if current_row.len() < play_col {
+                return Err(BattleshipError::parse(
+                    line_num,
+                    None,
+                    format!("Error: Not enough columns in row {} of {}, {} of {}", row_index + 1, label, current_row.len(), play_col),
+                ));
+            }
 
-                row_index += 1;
-            } else {
-                return Err(Box::new(LoadError {
-                    message: format!("Error: Not enough rows for player {}", player_num).to_string(),
-                }));
+            for (col_index, &cell) in current_row.iter().enumerate() {
+                if cell != 0 {
+                    tmp_ships_hash
+                        .entry(cell)
+                        .or_default()
+                        .insert(Coord { row: row_index, col: col_index });
+                }
             }
+            line_num += 1;
+        }
+        if truncated {                              // Lenient, the partial player is dropped
+            break;
         }
 
         // Process all ships for the current player
-        process_ships(&tmp_ships_hash, &mut player, myboard)?;
+        process_ships(&tmp_ships_hash, &mut player, myboard, first_row_line, &label)?;
 
         myboard.boards_add(player);
+        player_num += 1;
     }
 
+    if player_num < declared {
+        let message = format!("Header declares {} players but the file has {}.", declared, player_num);
+        if !lenient {
+            return Err(BattleshipError::parse(count_line, Some(1), format!("Error: {}", message)));
+        }
+        myboard.add_warning(BattleshipError::parse(count_line, Some(1), message));
+    }
+    myboard.set_playercount(player_num);
     Ok(())
 }
 
+// first_row_line is the 1 based file line of the player's first board row, used to place errors
 fn process_ships(
-    tmp_ships_hash: &HashMap<usize, HashSet<(usize, usize)>>,
+    tmp_ships_hash: &HashMap<usize, HashSet<Coord>>,
     player: &mut PlayBoard,
     myboard: &GameData,
-) -> Result<(), Box<dyn std::error::Error>> {
-    for (ship_id, ship_parts) in tmp_ships_hash.iter() {
+    first_row_line: usize,
+    label: &str,
+) -> Result<(), BattleshipError> {
+    let mut ship_ids: Vec<&usize> = tmp_ships_hash.keys().collect();
+    ship_ids.sort();                                // Same ship order on every load
+    for ship_id in ship_ids {
+        let ship_parts = &tmp_ships_hash[ship_id];
         let mut min_row = usize::MAX;
         let mut max_row = usize::MIN;
         let mut min_col = usize::MAX;
         let mut max_col = usize::MIN;
 
-        for &(row, col) in ship_parts.iter() {
-            min_row = min_row.min(row);
-            max_row = max_row.max(row);
-            min_col = min_col.min(col);
-            max_col = max_col.max(col);
+        for part in ship_parts.iter() {
+            min_row = min_row.min(part.row);
+            max_row = max_row.max(part.row);
+            min_col = min_col.min(part.col);
+            max_col = max_col.max(part.col);
         }
 
         let direction = if max_col > min_col {
@@ -201,79 +206,213 @@ fn process_ships(
         };
 
         let is_contiguous = if direction == Direction::Horizontal {
-            (min_col..=max_col).all(|col| ship_parts.contains(&(min_row, col)))
+            (min_col..=max_col).all(|col| ship_parts.contains(&Coord { row: min_row, col }))
         } else {
-            (min_row..=max_row).all(|row| ship_parts.contains(&(row, min_col)))
+            (min_row..=max_row).all(|row| ship_parts.contains(&Coord { row, col: min_col }))
         };
 
         if !is_contiguous || (if direction == Direction::Horizontal { max_col - min_col + 1 } else { max_row - min_row + 1 }) != *ship_id {
-            return Err(Box::new(LoadError {
-                message: format!("Error: Ship {} is not properly sized or has gaps", *ship_id),
-            }));
+            return Err(BattleshipError::parse(
+                first_row_line + min_row,
+                None,
+                format!("Error: Ship {} of {} is not properly sized or has gaps", *ship_id, label),
+            ));
         }
 
-        let ship = ShipBoundingBox::new(*ship_id, (min_col, min_row), direction, myboard, player);
-        if let Some(s) = ship {
-            player.add_ship(s);
+        let placed = ShipBoundingBox::new(*ship_id, Coord { row: min_row, col: min_col }, direction, myboard, player)
+            .map_err(BattleshipError::from)
+            .and_then(|ship| player.add_ship(ship));
+        if let Err(err) = placed {
+            let message = format!("{} ({})", err, label);
+            return Err(err.at(first_row_line + min_row, None, message));
         }
     }
 
     Ok(())
 }
 
-pub fn load_file(filename: &str, myboard: &mut GameData) -> Result<(), String> {
+// True for the first line of a v2 file, whatever version it claims
+pub fn is_v2_magic(line: &str) -> bool {
+    line.split_whitespace().next() == Some(V2_MAGIC)
+}
+
+// Check the magic line names a version this loader reads
+fn check_v2_version(line: &str) -> Result<(), BattleshipError> {
+    let version = line.split_whitespace().nth(1).unwrap_or("");
+    match version.trim_start_matches(['v', 'V']).parse::<usize>() {
+        Ok(V2_VERSION) => Ok(()),
+        _ => Err(BattleshipError::parse(1, Some(V2_MAGIC.len() + 2), format!("Error: Unsupported board file version '{}', expected v{}.", version, V2_VERSION))),
+    }
+}
+
+// Read the key=value headers of a v2 file after the magic line, up to and including V2_HEADER_END.
+// Blank lines and lines starting with # are skipped, unknown keys are kept as warnings so newer files still load.
+// Returns the 1 based lines of the first player and of the players header
+pub fn load_v2_header<R: BufRead>(lines: &mut std::io::Lines<R>, myboard: &mut GameData) -> Result<(usize, usize), BattleshipError> {
+    let mut line_num = 1;
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut ships: Option<(usize, usize)> = None;
+
+    loop {
+        line_num += 1;
+        let line = match lines.next() {
+            Some(line) => line.map_err(|err| BattleshipError::io(&format!("Error reading line {}", line_num), err))?,
+            None => return Err(BattleshipError::parse(line_num, None, format!("Error: The header never ends, expected a {} line.", V2_HEADER_END))),
+        };
+        let line = line.trim();
+        if line == V2_HEADER_END {
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+            None => return Err(BattleshipError::parse(line_num, Some(1), format!("Error: Expected a key=value header or {}.", V2_HEADER_END))),
+        };
+        if let Some(first) = seen.insert(key.clone(), line_num) {
+            return Err(BattleshipError::parse(line_num, Some(1), format!("Error: Header {} was already given on line {}.", key, first)));
+        }
+        let value_column = Some(line.find('=').unwrap_or(0) + 2);
+        let number = |value: &str| parse_to_usize(value).map_err(|err| {
+            err.at(line_num, value_column, format!("Error: Header {} must be a number from 1 to {}.", key, enums::MAX_SIZE))
+        });
+        match key.as_str() {
+            "rows" => myboard.set_row_or_col(number(value)?, true),
+            "cols" => myboard.set_row_or_col(number(value)?, false),
+            "players" => myboard.set_playercount(number(value)?),
+            "ships" => {
+                let (small, large) = value.split_once('-').unwrap_or((value, value));
+                ships = Some((number(small.trim())?, number(large.trim())?));
+            }
+            "rules" => myboard.set_rules(value.to_string()),
+            "seed" => {
+                let seed = value.parse::<u64>().map_err(|_| {
+                    BattleshipError::parse(line_num, value_column, format!("Error: Header seed must be a number, found '{}'.", value))
+                })?;
+                myboard.set_seed(seed);
+            }
+            "created-by" => {}                      // Only for people reading the file
+            _ => myboard.add_warning(BattleshipError::parse(line_num, Some(1), format!("Unknown header {} was ignored.", key))),
+        }
+    }
+
+    for required in ["rows", "cols", "players"] {
+        if !seen.contains_key(required) {
+            return Err(BattleshipError::parse(line_num, None, format!("Error: Missing the {} header.", required)));
+        }
+    }
+    if let Some((small, large)) = ships {
+        myboard.set_shipsizes(small, Some(large)).map_err(|err| {
+            let message = err.to_string();
+            err.at(seen["ships"], None, message)
+        })?;
+    }
+    Ok((line_num + 1, seen["players"]))
+}
+
+// Load a board file in any format, the format found is kept on myboard so a write gives the same layout back
+pub fn load_file(filename: &str, myboard: &mut GameData) -> Result<(), BattleshipError> {
+    load_file_format(filename, myboard, None)
+}
+
+// Load a board file as format, or work the format out from the first line when None
+pub fn load_file_format(filename: &str, myboard: &mut GameData, format: Option<FileFormat>) -> Result<(), BattleshipError> {
     if filename.is_empty() {
-        return Err("Error: Filename cannot be empty.".to_string());
+        return Err(BattleshipError::EmptyFilename);
     }
 
-    let file = File::open(filename).map_err(|err| format!("Error opening file: {}", err))?;
+    let file = File::open(filename).map_err(|err| BattleshipError::io("Error opening file", err))?;
     let reader = BufReader::new(file);
     let mut lines = reader.lines();
 
-    for (line_num, line) in lines.by_ref().enumerate() {
-        if line_num < 3 {
-            let line_content = line.map_err(|_| format!("Error reading line {}", line_num))?;
-            load_file_game_data(&line_content, myboard, line_num)?;
-        } else {
-            break;
-        }
+    let first = match lines.next() {
+        Some(line) => line.map_err(|err| BattleshipError::io("Error reading line 1", err))?,
+        None => return Err(BattleshipError::parse(1, None, "Error: Empty line".to_string())),
+    };
+    let found = if json::is_json(&first) {
+        FileFormat::Json
+    } else if is_v2_magic(&first) {
+        FileFormat::V2
+    } else {
+        FileFormat::Legacy
+    };
+    let format = format.unwrap_or(found);
+    if format != found && format != FileFormat::Json {      // JSON is parsed anyway, its errors say more
+        return Err(BattleshipError::parse(1, Some(1), format!("Error: Expected a {} file but found {}.", format.get_name(), found.get_name())));
     }
 
-    load_player_game_data(&mut lines, myboard)
-        .map_err(|err| format!("Error loading player game data: {}", err))?;
+    match format {
+        FileFormat::Json => {
+            let mut text = first;
+            for (line_num, line) in lines.enumerate() {
+                let line = line.map_err(|err| BattleshipError::io(&format!("Error reading line {}", line_num + 2), err))?;
+                text.push('\n');
+                text.push_str(&line);
+            }
+            json::load_json(&text, myboard)?;
+        }
+        FileFormat::V2 => {
+            check_v2_version(&first)?;
+            let (first_player, count_line) = load_v2_header(&mut lines, myboard)?;
+            load_player_game_data(&mut lines, myboard, first_player, count_line)?;
+        }
+        FileFormat::Legacy => {
+            load_file_game_data(&first, myboard, 0)?;
+            for (line_num, line) in lines.by_ref().take(HEADER_LINES - 1).enumerate() {   // Only the header, leave the players
+                let line_content = line.map_err(|err| BattleshipError::io(&format!("Error reading line {}", line_num + 2), err))?;
+                load_file_game_data(&line_content, myboard, line_num + 1)?;
+            }
+            load_player_game_data(&mut lines, myboard, HEADER_LINES + 1, HEADER_LINES)?;
+        }
+    }
 
+    myboard.set_format(format);
+    myboard.set_filename(filename.to_string());
     myboard.set_loaded(true);
     Ok(())
 }
 
+// Write the boards to the filename set on myboard in the format set on it.  Nothing is taken out of myboard, so
+// play can go on after.  Every format holds the size, fleet and ship placements, JSON holds the guesses too, and
+// none holds whose turn it is; loading the file back gives an equal GameData only before the first shot.
+// save.rs writes the whole game
+pub fn write_file(myboard: &GameData) -> Result<(), BattleshipError> {
+    let myfile = File::create(myboard.get_filename())
+        .map_err(|err| BattleshipError::io("Error: Failed to open specified file", err))?;
+    let mut writer = BufWriter::new(myfile);
+    write_boards(myboard, &mut writer).map_err(|err| BattleshipError::io("Error: Failed to write file", err))
+}
 
-pub fn write_file(myboard: &mut GameData) -> bool {
+// Header for the format on myboard then every player's name and rows
+fn write_boards<W: Write>(myboard: &GameData, writer: &mut W) -> std::io::Result<()> {
     let (my_cols, my_rows) = myboard.get_col_row();
-    let file = File::create(myboard.get_filename());
-    match file {
-        Err(err) => {
-            handle_file_error(err);
-            return false;
-        }
-        Ok(myfile) => {
-            let mut writer = BufWriter::new(myfile);
-            writeln!(writer, "{}\n{}\n{}", my_cols, my_rows, myboard.get_playercount());    // Write global data first
-            while let Some(mut playboard) = myboard.remove_first_board() {
-                let tmpboard = board::create_my_board_from_player(myboard, &mut playboard);             // Create a temp board to write
-                writeln!(writer, "{}", playboard.get_playername());
-                for row in tmpboard {
-                    let mut row_str = String::new();
-                    for &val in row.iter() {
-                        row_str.push_str(&val.to_string());
-                        row_str.push(',');
-                    }
-                    if !row_str.is_empty() {
-                        row_str.pop();
-                    }
-                    writeln!(writer, "{}", row_str);
-                }
+    match myboard.get_format() {
+        FileFormat::Legacy => writeln!(writer, "{}\n{}\n{}", my_rows, my_cols, myboard.get_boards_len())?,     // Same order load_file reads
+        FileFormat::V2 => {
+            let (small, large) = myboard.get_shipsizes();
+            writeln!(writer, "{} v{}", V2_MAGIC, V2_VERSION)?;
+            writeln!(writer, "rows={}\ncols={}\nplayers={}", my_rows, my_cols, myboard.get_boards_len())?;
+            writeln!(writer, "ships={}-{}\nrules={}", small, large, myboard.get_rules())?;
+            if myboard.get_seeded() {                   // A seed drawn at random says nothing about these boards
+                writeln!(writer, "seed={}", myboard.get_seed())?;
             }
+            writeln!(writer, "created-by={} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))?;
+            writeln!(writer, "{}", V2_HEADER_END)?;
+        }
+        FileFormat::Json => {                       // Players are in the document, nothing follows it
+            let text = json::game_to_json(myboard).map_err(std::io::Error::other)?;
+            writeln!(writer, "{}", text)?;
+            return writer.flush();
+        }
+    }
+    for playboard in myboard.get_boards() {
+        let tmpboard = board::create_my_board_from_player(myboard, playboard);                 // Create a temp board to write
+        writeln!(writer, "{}", playboard.get_playername())?;
+        for row in tmpboard {
+            let row_str: Vec<String> = row.iter().map(|val| val.to_string()).collect();
+            writeln!(writer, "{}", row_str.join(","))?;
         }
     }
-    return true;
+    writer.flush()
 }
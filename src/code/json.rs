@@ -0,0 +1,205 @@
+// JSON form of a game, for tools that cannot read the board files.  The schema is stable: fields are only
+// added, and anything that would break a reader bumps JSON_SCHEMA_VERSION.
+//
+// {
+//   "schema": "battleship",                        Always this string
+//   "version": 1,                                  JSON_SCHEMA_VERSION the file was written with
+//   "rows": 10,                                    Board size, 1 to 32767 each
+//   "cols": 10,
+//   "fleet": { "smallest": 2, "largest": 5 },      Ship ids in the fleet, a ship's id is also its length
+//   "rules": "classic",
+//   "seed": 42,                                    Seed of the game's random numbers, left out if none was given
+//   "players": [                                   In turn order
+//     {
+//       "name": "Ann",
+//       "number": 1,                               1 based position in players
+//       "ships": [
+//         { "id": 3, "start": { "row": 0, "col": 1 }, "end": { "row": 0, "col": 3 }, "direction": "horizontal" }
+//       ],
+//       "guesses": [                               Shots taken at this player's board
+//         { "at": { "row": 4, "col": 2 }, "result": "miss" }
+//       ]
+//     }
+//   ]
+// }
+//
+// Coordinates are 0 based, so row 0 col 1 is B1.  direction is "horizontal" or "vertical" and result is "hit"
+// or "miss".  end is always start moved id - 1 cells along direction; it is written so readers need not work it out.
+use serde::{Deserialize, Serialize};
+use crate::code::board::{GameData, PlayBoard, ShipBoundingBox};
+use crate::code::coord::Coord;
+use crate::code::enums;
+use crate::code::enums::{Direction, HitMiss};
+use crate::code::error::BattleshipError;
+
+pub const JSON_SCHEMA: &str = "battleship";
+pub const JSON_SCHEMA_VERSION: usize = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GameJson {
+    pub schema: String,
+    pub version: usize,
+    pub rows: usize,
+    pub cols: usize,
+    pub fleet: FleetJson,
+    pub rules: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    pub players: Vec<PlayerJson>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct FleetJson {
+    pub smallest: usize,
+    pub largest: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlayerJson {
+    pub name: String,
+    pub number: usize,
+    pub ships: Vec<ShipJson>,
+    pub guesses: Vec<GuessJson>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ShipJson {
+    pub id: usize,
+    pub start: Coord,
+    pub end: Coord,
+    pub direction: Direction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct GuessJson {
+    pub at: Coord,
+    pub result: HitMiss,
+}
+
+impl GameJson {
+    pub fn from_game(myboard: &GameData) -> Self {
+        let (cols, rows) = myboard.get_col_row();
+        let (smallest, largest) = myboard.get_shipsizes();
+        Self {
+            schema: JSON_SCHEMA.to_string(),
+            version: JSON_SCHEMA_VERSION,
+            rows,
+            cols,
+            fleet: FleetJson { smallest, largest },
+            rules: myboard.get_rules().to_string(),
+            seed: myboard.get_seeded().then(|| myboard.get_seed()),
+            players: myboard.get_boards().iter().enumerate().map(|(index, player)| PlayerJson::from_player(index, player)).collect(),
+        }
+    }
+}
+
+impl PlayerJson {
+    pub fn from_player(index: usize, player: &PlayBoard) -> Self {
+        let mut guesses: Vec<GuessJson> = player.get_hits().iter().map(|at| GuessJson { at, result: HitMiss::Hit })
+            .chain(player.get_misses().iter().map(|at| GuessJson { at, result: HitMiss::Miss }))
+            .collect();
+        guesses.sort_by_key(|guess| guess.at);
+        Self {
+            name: player.get_playername().clone(),
+            number: index + 1,
+            ships: player.return_ships().iter().map(ShipJson::from_ship).collect(),
+            guesses,
+        }
+    }
+}
+
+impl ShipJson {
+    pub fn from_ship(ship: &ShipBoundingBox) -> Self {
+        Self { id: ship.ship_id, start: ship.start, end: ship.end, direction: ship.get_direction() }
+    }
+}
+
+// Parse JSON text into the schema types, a syntax or type error is placed at its line and column
+pub fn parse_json(text: &str) -> Result<GameJson, BattleshipError> {
+    let game: GameJson = serde_json::from_str(text).map_err(|err| {
+        let position = format!(" at line {} column {}", err.line(), err.column());
+        let message = err.to_string();                  // serde_json puts the position on the end, Parse shows it already
+        BattleshipError::parse(err.line(), Some(err.column()), format!("Error: Invalid JSON, {}", message.trim_end_matches(&position)))
+    })?;
+    if game.schema != JSON_SCHEMA || game.version != JSON_SCHEMA_VERSION {
+        return Err(BattleshipError::Schema(format!(
+            "Error: Unsupported JSON schema {} version {}, expected {} version {}.",
+            game.schema, game.version, JSON_SCHEMA, JSON_SCHEMA_VERSION,
+        )));
+    }
+    Ok(game)
+}
+
+// Fill myboard from JSON text.  Ships are placed with the same rules as every other board and guesses are
+// replayed against them, so a hit must land on a ship and a miss must not
+pub fn load_json(text: &str, myboard: &mut GameData) -> Result<(), BattleshipError> {
+    let game = parse_json(text)?;
+    for (value, name) in [(game.rows, "rows"), (game.cols, "cols")] {
+        if !(enums::MIN_SIZE..=enums::MAX_SIZE).contains(&value) {
+            return Err(BattleshipError::Schema(format!("Error: {} must be from {} to {}, found {}.", name, enums::MIN_SIZE, enums::MAX_SIZE, value)));
+        }
+    }
+    myboard.set_col_row(game.cols, game.rows);
+    myboard.set_shipsizes(game.fleet.smallest, Some(game.fleet.largest))?;
+    myboard.set_rules(game.rules.clone());
+    if let Some(seed) = game.seed {
+        myboard.set_seed(seed);
+    }
+
+    for (index, player_json) in game.players.iter().enumerate() {
+        let label = format!("player {} ({})", index + 1, player_json.name);
+        if player_json.name.trim().is_empty() || player_json.name.contains(',') {
+            return Err(BattleshipError::Schema(format!("Error: Inappropriate or blank player name for {}.", label)));
+        }
+        if player_json.number != index + 1 {
+            return Err(BattleshipError::Schema(format!("Error: {} has number {}, expected {}.", label, player_json.number, index + 1)));
+        }
+        myboard.boards_add(player_from_json(myboard, index, player_json, &label)?);
+    }
+    myboard.set_playercount(game.players.len());
+    Ok(())
+}
+
+// Build one player, label names it in errors
+fn player_from_json(myboard: &GameData, index: usize, player_json: &PlayerJson, label: &str) -> Result<PlayBoard, BattleshipError> {
+    let mut player = PlayBoard::default();
+    player.set_playername(player_json.name.clone());
+    player.set_playernum(index);
+    let (cols, rows) = myboard.get_col_row();
+    player.set_dimensions(cols, rows);
+
+    for ship_json in player_json.ships.iter() {
+        let ship = ShipBoundingBox::new(ship_json.id, ship_json.start, ship_json.direction, myboard, &player)
+            .map_err(|err| BattleshipError::Schema(format!("{} ({})", err, label)))?;
+        if ship.end != ship_json.end {
+            return Err(BattleshipError::Schema(format!(
+                "Error: Ship {} of {} ends at {}, expected {}.", ship_json.id, label, ship_json.end, ship.end,
+            )));
+        }
+        player.add_ship(ship)?;
+    }
+    for guess in player_json.guesses.iter() {
+        let landed = player.handle_shot(guess.at);
+        let message = match (guess.result, landed) {
+            (HitMiss::Hit, None) => Some("a hit but there is no ship there".to_string()),
+            (HitMiss::Miss, Some(ship_id)) => Some(format!("a miss but ship {} is there", ship_id)),
+            _ => None,
+        };
+        if let Some(message) = message {
+            return Err(BattleshipError::Schema(format!("Error: Guess {} at {} is recorded as {}.", guess.at, label, message)));
+        }
+        player.take_shot(guess.at).map_err(|err| BattleshipError::Schema(format!("{} ({})", err, label)))?;
+    }
+    Ok(player)
+}
+
+// The whole game as indented JSON text
+pub fn game_to_json(myboard: &GameData) -> Result<String, BattleshipError> {
+    serde_json::to_string_pretty(&GameJson::from_game(myboard))
+        .map_err(|err| BattleshipError::Schema(format!("Error: Failed to build JSON, {}", err)))
+}
+
+// True when text looks like a JSON board file rather than one of the line formats
+pub fn is_json(text: &str) -> bool {
+    text.trim_start().starts_with('{')
+}
@@ -1,10 +1,17 @@
+pub mod arena;
+pub mod bitboard;
 pub mod board;
-pub mod commandline;
+pub mod coord;
 pub mod datacheck;
 pub mod enums;
+pub mod error;
 pub mod file;
-pub mod handler;
-pub mod interactive;
-pub mod ship;
+pub mod json;
+pub mod placement;
+pub mod save;
+pub mod session;
+pub mod strategy;
+pub mod transcript;
 pub mod utils;
+pub mod verify;
 pub mod aistuff;
\ No newline at end of file
@@ -0,0 +1,217 @@
+use crate::code::bitboard::BitBoard;
+use crate::code::board::{GameData, PlayBoard};
+use crate::code::coord::Coord;
+use crate::code::enums::Direction;
+use crate::code::utils::GameRng;
+use rand::Rng;
+
+// Occupancy of one board kept as runs of free cells along every row and every column.
+// Adding or removing a ship only touches the one row and the columns it crosses (or the reverse),
+// so the placements for a ship size can be counted, listed or sampled without rescanning the board.
+pub struct PlacementMask {
+    cols: usize,
+    rows: usize,
+    occupied: BitBoard,
+    row_runs: Vec<Vec<(usize, usize)>>,             // Per row, free runs as (start col, length) in order
+    col_runs: Vec<Vec<(usize, usize)>>,             // Per column, free runs as (start row, length) in order
+}
+
+impl PlacementMask {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            occupied: BitBoard::new(cols, rows),
+            row_runs: vec![if cols > 0 { vec![(0, cols)] } else { Vec::new() }; rows],
+            col_runs: vec![if rows > 0 { vec![(0, rows)] } else { Vec::new() }; cols],
+        }
+    }
+
+    // Mask of a game board with the player's ships already taken out
+    pub fn from_player(myboard: &GameData, player: &PlayBoard) -> Self {
+        let (cols, rows) = myboard.get_col_row();
+        let mut mask = PlacementMask::new(cols, rows);
+        for ship in player.return_ships() {
+            mask.occupy(ship.start, ship.length(), ship.get_direction());
+        }
+        mask
+    }
+
+    pub fn get_occupied(&self) -> &BitBoard {
+        &self.occupied
+    }
+
+    pub fn is_free(&self, coord: Coord) -> bool {
+        coord.col < self.cols && coord.row < self.rows && !self.occupied.test(coord)
+    }
+
+    // True if a ship of ship_size at start stays on the board and only covers free cells
+    pub fn fits(&self, start: Coord, ship_size: usize, direction: Direction) -> bool {
+        let Coord { row, col } = start;
+        if ship_size == 0 || col >= self.cols || row >= self.rows {
+            return false;
+        }
+        let (runs, along) = match direction {
+            Direction::Horizontal => (&self.row_runs[row], col),
+            Direction::Vertical => (&self.col_runs[col], row),
+        };
+        match find_run(runs, along) {
+            Some(index) => {
+                let (run_start, run_len) = runs[index];
+                along + ship_size <= run_start + run_len
+            }
+            None => false,
+        }
+    }
+
+    // Number of places a ship of ship_size could go
+    pub fn count(&self, ship_size: usize) -> usize {
+        if ship_size == 0 {
+            return 0;
+        }
+        let fits_in = |runs: &Vec<(usize, usize)>| -> usize {
+            runs.iter().map(|&(_, len)| (len + 1).saturating_sub(ship_size)).sum()
+        };
+        self.row_runs.iter().map(fits_in).sum::<usize>() + self.col_runs.iter().map(fits_in).sum::<usize>()
+    }
+
+    // Every place a ship of ship_size could go, as the start cell and direction
+    pub fn placements(&self, ship_size: usize) -> Vec<(Coord, Direction)> {
+        let mut found = Vec::with_capacity(self.count(ship_size));
+        if ship_size == 0 {
+            return found;
+        }
+        for (row, runs) in self.row_runs.iter().enumerate() {
+            for &(start, len) in runs {
+                for col in start..(start + len + 1).saturating_sub(ship_size) {
+                    found.push((Coord { row, col }, Direction::Horizontal));
+                }
+            }
+        }
+        for (col, runs) in self.col_runs.iter().enumerate() {
+            for &(start, len) in runs {
+                for row in start..(start + len + 1).saturating_sub(ship_size) {
+                    found.push((Coord { row, col }, Direction::Vertical));
+                }
+            }
+        }
+        found
+    }
+
+    // One placement drawn uniformly from every place a ship of ship_size could go
+    pub fn sample(&self, ship_size: usize, rng: &mut GameRng) -> Option<(Coord, Direction)> {
+        let total = self.count(ship_size);
+        if total == 0 {
+            return None;
+        }
+        let mut pick = rng.random_range(0..total);
+        for (row, runs) in self.row_runs.iter().enumerate() {
+            for &(start, len) in runs {
+                let here = (len + 1).saturating_sub(ship_size);
+                if pick < here {
+                    return Some((Coord { row, col: start + pick }, Direction::Horizontal));
+                }
+                pick -= here;
+            }
+        }
+        for (col, runs) in self.col_runs.iter().enumerate() {
+            for &(start, len) in runs {
+                let here = (len + 1).saturating_sub(ship_size);
+                if pick < here {
+                    return Some((Coord { row: start + pick, col }, Direction::Vertical));
+                }
+                pick -= here;
+            }
+        }
+        None
+    }
+
+    // Take the cells of a ship out of the free runs.  The caller checks fits first
+    pub fn occupy(&mut self, start: Coord, ship_size: usize, direction: Direction) {
+        for point in start.line(direction, ship_size) {
+            self.occupied.set(point);
+        }
+        let Coord { row, col } = start;
+        match direction {
+            Direction::Horizontal => {
+                split_run(&mut self.row_runs[row], col, ship_size);
+                for c in col..col + ship_size {
+                    split_run(&mut self.col_runs[c], row, 1);
+                }
+            }
+            Direction::Vertical => {
+                split_run(&mut self.col_runs[col], row, ship_size);
+                for r in row..row + ship_size {
+                    split_run(&mut self.row_runs[r], col, 1);
+                }
+            }
+        }
+    }
+
+    // Give the cells of a ship back to the free runs
+    pub fn release(&mut self, start: Coord, ship_size: usize, direction: Direction) {
+        for point in start.line(direction, ship_size) {
+            self.occupied.clear(point);
+        }
+        let Coord { row, col } = start;
+        match direction {
+            Direction::Horizontal => {
+                merge_run(&mut self.row_runs[row], col, ship_size);
+                for c in col..col + ship_size {
+                    merge_run(&mut self.col_runs[c], row, 1);
+                }
+            }
+            Direction::Vertical => {
+                merge_run(&mut self.col_runs[col], row, ship_size);
+                for r in row..row + ship_size {
+                    merge_run(&mut self.row_runs[r], col, 1);
+                }
+            }
+        }
+    }
+}
+
+// Index of the run holding position, runs are sorted and do not overlap
+fn find_run(runs: &[(usize, usize)], position: usize) -> Option<usize> {
+    let index = runs.partition_point(|&(start, _)| start <= position).checked_sub(1)?;
+    let (start, len) = runs[index];
+    if position < start + len { Some(index) } else { None }
+}
+
+// Remove start..start+len from the run that holds it, leaving the free pieces either side
+fn split_run(runs: &mut Vec<(usize, usize)>, start: usize, len: usize) {
+    let index = match find_run(runs, start) {
+        Some(index) => index,
+        None => return,
+    };
+    let (run_start, run_len) = runs[index];
+    let before = (run_start, start - run_start);
+    let after_start = start + len;
+    let after = (after_start, (run_start + run_len).saturating_sub(after_start));
+    let mut pieces = Vec::with_capacity(2);
+    if before.1 > 0 {
+        pieces.push(before);
+    }
+    if after.1 > 0 {
+        pieces.push(after);
+    }
+    runs.splice(index..=index, pieces);
+}
+
+// Add start..start+len back as free, joining the runs it touches
+fn merge_run(runs: &mut Vec<(usize, usize)>, start: usize, len: usize) {
+    let index = runs.partition_point(|&(run_start, _)| run_start < start);
+    let mut new_start = start;
+    let mut new_end = start + len;
+    let mut first = index;
+    let mut last = index;
+    if index > 0 && runs[index - 1].0 + runs[index - 1].1 == start {
+        first = index - 1;
+        new_start = runs[first].0;
+    }
+    if index < runs.len() && runs[index].0 == new_end {
+        new_end = runs[index].0 + runs[index].1;
+        last = index + 1;
+    }
+    runs.splice(first..last, [(new_start, new_end - new_start)]);
+}
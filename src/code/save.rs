@@ -0,0 +1,202 @@
+// A paused game.  The boards, ships and guesses are the JSON game from json.rs; the rest is what a board file
+// leaves out so a resumed game carries on exactly where it stopped.
+//
+// {
+//   "schema": "battleship-save",
+//   "version": 1,                                  SAVE_SCHEMA_VERSION
+//   "game": { ... },                               A JSON game, see json.rs
+//   "turn": 14,                                    Shots fired so far
+//   "current": 2,                                  1 based player whose turn it is
+//   "eliminated": [3],                             1 based players out of the game, in the order they went out
+//   "sunk": [[2, 4], [], []],                      Ship ids sunk, one list per player
+//   "rng": {                                       Where the game's generator is, see GameRng
+//     "key": "00ff...",                              64 hex digits
+//     "stream": 0,
+//     "word_pos": 1234                               Words drawn so far
+//   },
+//   "ai": {
+//     "level": "probability",                      Intelligence::get_name
+//     "heat_map": [[0, 3, ...], ...]               Row, Col counts behind the AI's last shot, [] until it fires
+//   }
+// }
+//
+// sunk can be worked out from the guesses and is written for readers, resume checks it agrees.
+// Saving only reads the generator, so a run that saves draws the same numbers as one that does not,
+// and the resumed game carries on from the same place in the stream.
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use serde::{Deserialize, Serialize};
+use crate::code::board::GameData;
+use crate::code::enums::Intelligence;
+use crate::code::error::BattleshipError;
+use crate::code::json;
+use crate::code::json::GameJson;
+use crate::code::utils::GameRng;
+use rand::SeedableRng;
+
+pub const SAVE_SCHEMA: &str = "battleship-save";
+pub const SAVE_SCHEMA_VERSION: usize = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveJson {
+    pub schema: String,
+    pub version: usize,
+    pub game: GameJson,
+    pub turn: usize,
+    pub current: usize,
+    pub eliminated: Vec<usize>,
+    pub sunk: Vec<Vec<usize>>,
+    pub rng: RngJson,
+    pub ai: AiJson,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RngJson {
+    pub key: String,
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AiJson {
+    pub level: String,
+    #[serde(default)]
+    pub heat_map: Vec<Vec<usize>>,
+}
+
+impl RngJson {
+    pub fn from_rng(rng: &GameRng) -> Self {
+        RngJson {
+            key: rng.get_seed().iter().map(|byte| format!("{:02x}", byte)).collect(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
+        }
+    }
+
+    pub fn to_rng(&self) -> Result<GameRng, BattleshipError> {
+        let bad_key = || BattleshipError::Schema(format!("Error: Generator key {} is not 64 hex digits.", self.key));
+        if self.key.len() != 64 || !self.key.is_ascii() {
+            return Err(bad_key());
+        }
+        let mut key = [0u8; 32];
+        for (num, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&self.key[num * 2..num * 2 + 2], 16).map_err(|_| bad_key())?;
+        }
+        let mut rng = GameRng::from_seed(key);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(self.word_pos);
+        Ok(rng)
+    }
+}
+
+// Snapshot of myboard, the generator included
+pub fn save_state(myboard: &GameData) -> SaveJson {
+    SaveJson {
+        schema: SAVE_SCHEMA.to_string(),
+        version: SAVE_SCHEMA_VERSION,
+        game: GameJson::from_game(myboard),
+        turn: myboard.get_turn(),
+        current: myboard.get_current_player() + 1,
+        eliminated: myboard.get_eliminated().iter().map(|&num| num + 1).collect(),
+        sunk: sunk_ships(myboard),
+        rng: RngJson::from_rng(myboard.get_rng_state()),
+        ai: AiJson {
+            level: myboard.get_ai_level().get_name().to_string(),
+            heat_map: myboard.get_ai_heat_map().clone(),
+        },
+    }
+}
+
+// Ids of the sunk ships of every player
+fn sunk_ships(myboard: &GameData) -> Vec<Vec<usize>> {
+    myboard.get_boards().iter()
+        .map(|player| player.return_ships().iter().filter(|ship| ship.is_sunk()).map(|ship| ship.ship_id).collect())
+        .collect()
+}
+
+pub fn save_game(myboard: &GameData, filename: &str) -> Result<(), BattleshipError> {
+    if filename.is_empty() {
+        return Err(BattleshipError::EmptyFilename);
+    }
+    let text = serde_json::to_string_pretty(&save_state(myboard))
+        .map_err(|err| BattleshipError::Schema(format!("Error: Failed to build JSON, {}", err)))?;
+    let myfile = File::create(filename).map_err(|err| BattleshipError::io("Error: Failed to open specified file", err))?;
+    let write_error = |err| BattleshipError::io("Error: Failed to write file", err);
+    let mut writer = BufWriter::new(myfile);
+    writeln!(writer, "{}", text).map_err(write_error)?;
+    writer.flush().map_err(write_error)?;
+    Ok(())
+}
+
+// Replace myboard with a saved game.  The boards are rebuilt by replaying every guess, then the
+// eliminations and sunk ships in the save have to agree with them
+pub fn resume_game(filename: &str, myboard: &mut GameData) -> Result<(), BattleshipError> {
+    if filename.is_empty() {
+        return Err(BattleshipError::EmptyFilename);
+    }
+    let mut text = String::new();
+    File::open(filename)
+        .and_then(|mut file| file.read_to_string(&mut text))
+        .map_err(|err| BattleshipError::io("Error opening file", err))?;
+    let save: SaveJson = serde_json::from_str(&text).map_err(|err| {
+        let position = format!(" at line {} column {}", err.line(), err.column());
+        let message = err.to_string();
+        BattleshipError::parse(err.line(), Some(err.column()), format!("Error: Invalid save, {}", message.trim_end_matches(&position)))
+    })?;
+    if save.schema != SAVE_SCHEMA || save.version != SAVE_SCHEMA_VERSION {
+        return Err(BattleshipError::Schema(format!(
+            "Error: Unsupported save schema {} version {}, expected {} version {}.",
+            save.schema, save.version, SAVE_SCHEMA, SAVE_SCHEMA_VERSION,
+        )));
+    }
+
+    let game_text = serde_json::to_string(&save.game)
+        .map_err(|err| BattleshipError::Schema(format!("Error: Failed to read the saved game, {}", err)))?;
+    let mut resumed = GameData::default();
+    resumed.set_lenient(myboard.get_lenient());
+    json::load_json(&game_text, &mut resumed)?;
+
+    let players = resumed.get_boards_len();
+    let in_range = |num: usize| num >= 1 && num <= players;
+    if !in_range(save.current) {
+        return Err(BattleshipError::Schema(format!("Error: Player {} cannot have the turn, the game has {} players.", save.current, players)));
+    }
+    if let Some(&bad) = save.eliminated.iter().find(|&&num| !in_range(num)) {
+        return Err(BattleshipError::Schema(format!("Error: Eliminated player {} is not in the game.", bad)));
+    }
+    let level = Intelligence::parse(&save.ai.level)
+        .ok_or_else(|| BattleshipError::Schema(format!("Error: Unknown AI level {}.", save.ai.level)))?;
+    let (max_col, max_row) = resumed.get_col_row();
+    let heat_map = &save.ai.heat_map;
+    if !heat_map.is_empty() && (heat_map.len() != max_row || heat_map.iter().any(|row| row.len() != max_col)) {
+        return Err(BattleshipError::Schema(format!("Error: The AI heat map is not {} rows of {} columns.", max_row, max_col)));
+    }
+    let rng = save.rng.to_rng()?;
+
+    resumed.set_turn(save.turn);
+    resumed.set_current_player(save.current - 1);
+    resumed.set_eliminated(save.eliminated.iter().map(|&num| num - 1).collect());
+    resumed.set_ai_level(level);
+    resumed.set_ai_heat_map(save.ai.heat_map);
+    resumed.set_rng(rng);
+    if save.turn == 0 {
+        resumed.eliminate_empty_boards();               // As the first shot would, a save from before it need not list them
+    }
+
+    if sunk_ships(&resumed) != save.sunk {
+        return Err(BattleshipError::Schema("Error: The sunk ships in the save do not match its guesses.".to_string()));
+    }
+    for num in 0..players {
+        let listed = resumed.get_eliminated().contains(&num);
+        if resumed.is_alive(num) && listed {
+            return Err(BattleshipError::Schema(format!("Error: Player {} is listed as eliminated but has ships afloat.", num + 1)));
+        }
+        if !resumed.is_alive(num) && !listed {
+            return Err(BattleshipError::Schema(format!("Error: Player {} has no ships afloat but is not listed as eliminated.", num + 1)));
+        }
+    }
+
+    resumed.set_loaded(true);
+    *myboard = resumed;
+    Ok(())
+}
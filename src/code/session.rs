@@ -0,0 +1,130 @@
+use crate::code::board::GameData;
+use crate::code::coord::Coord;
+use crate::code::enums::ShotResult;
+use crate::code::error::BattleshipError;
+use crate::code::strategy::{ObservationBoard, Strategy};
+
+// Everything that happened on one turn
+#[derive(PartialEq, Clone, Debug)]
+pub struct TurnReport {
+    pub turn: usize,
+    pub shooter: usize,
+    pub target: usize,
+    pub coord: Coord,
+    pub result: ShotResult,
+    pub eliminated: bool,                           // Target lost their last ship on this shot
+}
+
+// Final placing of every player, winner first
+#[derive(PartialEq, Clone, Debug)]
+pub struct GameResult {
+    pub winner: usize,
+    pub standings: Vec<usize>,
+    pub turns: usize,
+}
+
+// A game in progress.  Players are referenced by their index in the boards of GameData, whose turn it is
+// lives on the GameData as well so a saved game picks up where it stopped
+pub struct GameSession {
+    game: GameData,
+}
+
+impl GameSession {
+    pub fn new(mut game: GameData) -> Result<Self, BattleshipError> {
+        if game.get_boards_len() < 2 {
+            return Err(BattleshipError::NotEnoughPlayers);
+        }
+        if game.get_current_player() >= game.get_boards_len() {
+            return Err(BattleshipError::InvalidTarget(game.get_current_player()));
+        }
+        game.eliminate_empty_boards();                      // Nothing to shoot at, out before the start
+        Ok(Self { game })
+    }
+
+    pub fn get_game(&self) -> &GameData {
+        &self.game
+    }
+
+    pub fn get_game_mut(&mut self) -> &mut GameData {
+        &mut self.game
+    }
+
+    pub fn into_game(self) -> GameData {
+        self.game
+    }
+
+    pub fn get_current_player(&self) -> usize {
+        self.game.get_current_player()
+    }
+
+    pub fn get_turn(&self) -> usize {
+        self.game.get_turn()
+    }
+
+    pub fn is_alive(&self, player: usize) -> bool {
+        self.game.is_alive(player)
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.game.alive_count()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.alive_count() <= 1
+    }
+
+    // Every player the current player may shoot at
+    pub fn targets(&self) -> Vec<usize> {
+        let current = self.get_current_player();
+        (0..self.game.get_boards_len())
+            .filter(|&num| num != current && self.is_alive(num))
+            .collect()
+    }
+
+    // The next player still in the game after the current one
+    pub fn default_target(&self) -> Option<usize> {
+        if self.is_over() {
+            return None;
+        }
+        Some(self.game.next_alive(self.get_current_player()))
+    }
+
+    // The current player fires at target.  Illegal shots return an error and do not use up the turn
+    pub fn fire(&mut self, target: usize, coord: Coord) -> Result<TurnReport, BattleshipError> {
+        self.game.fire(target, coord)
+    }
+
+    // Let a strategy take the current player's turn against their default target
+    pub fn play_turn(&mut self, player_strategy: &mut dyn Strategy) -> Result<TurnReport, BattleshipError> {
+        let target = match self.default_target() {
+            Some(target) => target,
+            None => return Err(BattleshipError::GameOver),
+        };
+        let observed = match self.game.boards_get_player(target) {
+            Some(board) => ObservationBoard::from_player(&self.game, board),
+            None => return Err(BattleshipError::InvalidTarget(target)),
+        };
+        let coord = match player_strategy.choose_shot(&observed, self.game.get_rng()) {
+            Some(shot) => shot,
+            None => return Err(BattleshipError::NoShot(player_strategy.get_name())),
+        };
+        let report = self.fire(target, coord)?;
+        player_strategy.shot_result(report.coord, report.result);
+        Ok(report)
+    }
+
+    // Standings are only known once a single player is left
+    pub fn result(&self) -> Option<GameResult> {
+        if !self.is_over() {
+            return None;
+        }
+        let winner = (0..self.game.get_boards_len()).find(|&num| self.is_alive(num))?;
+        let mut standings = vec![winner];
+        standings.extend(self.game.get_eliminated().iter().rev());
+        Some(GameResult {
+            winner,
+            standings,
+            turns: self.game.get_turn(),
+        })
+    }
+}
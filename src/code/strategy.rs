@@ -0,0 +1,223 @@
+use crate::code::bitboard::BitBoard;
+use crate::code::board::{GameData, PlayBoard, ShipBoundingBox};
+use crate::code::coord::Coord;
+use crate::code::enums::{Direction, ShotResult};
+use crate::code::error::BattleshipError;
+use crate::code::placement::PlacementMask;
+use crate::code::utils;
+use crate::code::utils::GameRng;
+use rand::seq::SliceRandom;
+
+// What a shooter knows about a cell on the target
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CellState {
+    Unknown,
+    Miss,
+    Hit,                                            // Hit on a ship still afloat
+    Sunk,
+}
+
+// The public view of an opponent's board: shot results, the ships already sunk and the sizes still afloat
+pub struct ObservationBoard {
+    cols: usize,
+    rows: usize,
+    misses: BitBoard,
+    hits: BitBoard,                                 // Hits on ships still afloat
+    sunk: BitBoard,                                 // Hits on ships that have been sunk
+    sunk_ships: Vec<ShipBoundingBox>,
+    remaining_sizes: Vec<usize>,
+}
+
+impl ObservationBoard {
+    pub fn from_player(myboard: &GameData, target: &PlayBoard) -> Self {
+        let (cols, rows) = myboard.get_col_row();
+        let mut misses = BitBoard::new(cols, rows);
+        let mut hits = BitBoard::new(cols, rows);
+        let mut sunk = BitBoard::new(cols, rows);
+        for coord in target.get_misses().iter() {
+            misses.set(coord);
+        }
+        for coord in target.get_hits().iter() {
+            if target.ship_at(coord).is_some_and(|ship| ship.is_sunk()) {
+                sunk.set(coord);
+            } else {
+                hits.set(coord);
+            }
+        }
+        Self {
+            cols,
+            rows,
+            misses,
+            hits,
+            sunk,
+            sunk_ships: target.return_ships().iter().filter(|ship| ship.is_sunk()).cloned().collect(),
+            remaining_sizes: target.return_ships().iter().filter(|ship| !ship.is_sunk()).map(|ship| ship.length()).collect(),
+        }
+    }
+
+    pub fn get_col_row(&self) -> (usize, usize) {
+        (self.cols, self.rows)
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        coord.row < self.rows && coord.col < self.cols
+    }
+
+    pub fn get_cell(&self, coord: Coord) -> CellState {
+        if self.misses.test(coord) {
+            CellState::Miss
+        } else if self.hits.test(coord) {
+            CellState::Hit
+        } else if self.sunk.test(coord) {
+            CellState::Sunk
+        } else {
+            CellState::Unknown
+        }
+    }
+
+    // Bit mask of every cell in the given state
+    pub fn state_mask(&self, wanted: CellState) -> BitBoard {
+        match wanted {
+            CellState::Miss => self.misses.clone(),
+            CellState::Hit => self.hits.clone(),
+            CellState::Sunk => self.sunk.clone(),
+            CellState::Unknown => self.misses.union(&self.hits).union(&self.sunk).invert(),
+        }
+    }
+
+    pub fn get_sunk_ships(&self) -> &[ShipBoundingBox] {
+        &self.sunk_ships
+    }
+
+    pub fn get_remaining_sizes(&self) -> &[usize] {
+        &self.remaining_sizes
+    }
+
+    // Every cell in the given state, row by row
+    pub fn cells_in_state(&self, wanted: CellState) -> Vec<Coord> {
+        self.state_mask(wanted).iter().collect()
+    }
+}
+
+// A bot: places its own fleet and picks shots against an opponent
+pub trait Strategy {
+    fn get_name(&self) -> String;
+
+    // Fill player with the fleet smallestship..=largestship from myboard, an error if the fleet cannot fit.
+    // Ships go at random unless the strategy has its own layout
+    fn place_fleet(&mut self, myboard: &GameData, player: &mut PlayBoard, rng: &mut GameRng) -> Result<(), BattleshipError> {
+        random_fleet(myboard, player, rng)
+    }
+
+    // Next shot against the observed board, None when there is nothing left to shoot
+    fn choose_shot(&mut self, observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord>;
+
+    // Told the result of every shot this strategy fired
+    fn shot_result(&mut self, _coord: Coord, _result: ShotResult) {}
+}
+
+// Places ships at random and shoots at random
+#[derive(Default)]
+pub struct RandomPlacer;
+
+impl Strategy for RandomPlacer {
+    fn get_name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn choose_shot(&mut self, observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord> {
+        let candidates = observed.cells_in_state(CellState::Unknown);
+        utils::pick_random(&candidates, rng).copied()
+    }
+}
+
+// Placements tried per randomized search before starting over, random spots tried for each ship
+// before backing out, and how many fresh starts to make before a search that tries everything
+const PLACEMENT_BUDGET: usize = 20_000;
+const SAMPLES_PER_SHIP: usize = 16;
+const PLACEMENT_RESTARTS: usize = 8;
+// Placements the exhaustive search may try before the fleet is given up on
+const EXHAUSTIVE_BUDGET: usize = 50_000;
+
+// Place every ship of smallestship..=largestship the player does not have yet, largest first.
+// Backtracks when a ship has no room, so the result is always the whole fleet.  An error is
+// returned when the fleet cannot fit or the exhaustive search runs out of budget.
+pub fn random_fleet(myboard: &GameData, player: &mut PlayBoard, rng: &mut GameRng) -> Result<(), BattleshipError> {
+    let (small, large) = myboard.get_shipsizes();
+    let (max_col, max_row) = myboard.get_col_row();
+    let sizes: Vec<usize> = (small..=large).rev().filter(|&size| !player.player_has_ship_id(size)).collect();
+
+    let needed: usize = sizes.iter().sum::<usize>()
+        + player.return_ships().iter().map(|ship| ship.length()).sum::<usize>();
+    if needed > max_col * max_row || sizes.first().is_some_and(|&size| size > max_col.max(max_row)) {
+        return Err(BattleshipError::FleetTooLarge { small, large, cols: max_col, rows: max_row });
+    }
+    if !lines_suffice(&sizes, max_col, max_row) || !lines_suffice(&sizes, max_row, max_col) {
+        return Err(BattleshipError::NoArrangement { small, large, cols: max_col, rows: max_row });
+    }
+
+    let mut mask = PlacementMask::from_player(myboard, player);
+    let mut chosen = Vec::with_capacity(sizes.len());
+    let mut found = false;
+    for _ in 0..PLACEMENT_RESTARTS {
+        let mut budget = PLACEMENT_BUDGET;
+        if place_remaining(&mut mask, &sizes, rng, true, &mut budget, &mut chosen) {
+            found = true;
+            break;
+        }
+    }
+    let mut budget = EXHAUSTIVE_BUDGET;
+    if !found && !place_remaining(&mut mask, &sizes, rng, false, &mut budget, &mut chosen) {
+        return Err(BattleshipError::NoArrangement { small, large, cols: max_col, rows: max_row });
+    }
+
+    for (ship_size, position, direction) in chosen {
+        let ship = ShipBoundingBox::new(ship_size, position, direction, myboard, player)?;
+        player.add_ship(ship)?;
+    }
+    Ok(())
+}
+
+// Ships longer than the cross length of the board only fit along it, one to a line when longer than half a
+// line.  False when there are more of those than lines.  Called both ways round for rows and columns
+fn lines_suffice(sizes: &[usize], length: usize, lines: usize) -> bool {
+    sizes.iter().filter(|&&size| size > lines && size * 2 > length).count() <= lines
+}
+
+// Depth first search over the mask.  Sampled, each ship tries a few random spots, otherwise every
+// spot is tried in random order.  Each spot tried uses up budget.  Chosen placements are pushed on success
+fn place_remaining(
+    mask: &mut PlacementMask,
+    sizes: &[usize],
+    rng: &mut GameRng,
+    sampled: bool,
+    budget: &mut usize,
+    chosen: &mut Vec<(usize, Coord, Direction)>,
+) -> bool {
+    let ship_size = match sizes.first() {
+        Some(&size) => size,
+        None => return true,
+    };
+    let candidates: Vec<(Coord, Direction)> = if sampled {
+        (0..SAMPLES_PER_SHIP).filter_map(|_| mask.sample(ship_size, rng)).collect()
+    } else {
+        let mut every = mask.placements(ship_size);
+        every.shuffle(rng);
+        every
+    };
+
+    for (position, direction) in candidates {
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+        mask.occupy(position, ship_size, direction);
+        chosen.push((ship_size, position, direction));
+        if place_remaining(mask, &sizes[1..], rng, sampled, budget, chosen) {
+            return true;
+        }
+        chosen.pop();
+        mask.release(position, ship_size, direction);       // Back out and try the next spot
+    }
+    false
+}
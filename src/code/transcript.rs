@@ -0,0 +1,319 @@
+// Transcript of a game, a line per shot in a notation modelled on chess PGN.  Tag pairs name the boards the
+// game started from, the moves follow in turn order and are appended as they are played.
+//
+// [Event "Battleship"]
+// [Board "fleet.txt"]                              Board file the game started from, required
+// [Format "legacy"]                                FileFormat::get_name of the board file
+// [Seed "42"]                                      Only when the game was given a seed
+// [Players "3"]
+//
+// 1. 1x2 E6 miss                                   Turn. shooter x target, 1 based, then the coordinate
+// 2. 2x3 B4 hit 3                                  Hits and sinks carry the ship id
+// 3. 3x1 C7 sunk 2
+// 4. 1x2 E7 sunk 4 out                             out when the target has no ships left afloat
+//
+// Lines starting with ; are comments.  A relative Board is looked for from the working directory, then next
+// to the transcript.  Replaying fires every move at the starting boards and the results must agree.
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+use crate::code::board::{GameData, PlayBoard};
+use crate::code::coord::Coord;
+use crate::code::enums::{FileFormat, ShotResult};
+use crate::code::error::BattleshipError;
+use crate::code::file;
+use crate::code::session::TurnReport;
+
+pub const TRANSCRIPT_EVENT: &str = "Battleship";
+
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Transcript {
+    pub tags: Vec<(String, String)>,                // In the order they were written
+    pub moves: Vec<TurnReport>,
+}
+
+impl Transcript {
+    // Tags for a game about to start from the boards in myboard, loaded from board
+    pub fn new(myboard: &GameData, board: &str) -> Self {
+        let mut tags = vec![
+            ("Event", TRANSCRIPT_EVENT.to_string()),
+            ("Board", board.to_string()),
+            ("Format", myboard.get_format().get_name().to_string()),
+        ];
+        if myboard.get_seeded() {
+            tags.push(("Seed", myboard.get_seed().to_string()));
+        }
+        tags.push(("Players", myboard.get_boards_len().to_string()));
+        Self {
+            tags: tags.into_iter().map(|(key, value)| (key.to_string(), value)).collect(),
+            moves: Vec::new(),
+        }
+    }
+
+    pub fn get_tag(&self, key: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == key).map(|(_, value)| value.as_str())
+    }
+
+    // The tag pairs and a blank line, moves are written after it
+    pub fn header(&self) -> String {
+        let mut out: String = self.tags.iter().map(|(key, value)| format!("[{} \"{}\"]\n", key, value)).collect();
+        out.push('\n');
+        out
+    }
+}
+
+// One move in transcript notation, without a line end
+pub fn format_move(report: &TurnReport) -> String {
+    let out = if report.eliminated { " out" } else { "" };
+    format!("{}. {}x{} {} {}{}", report.turn, report.shooter + 1, report.target + 1, report.coord, format_result(report.result), out)
+}
+
+fn format_result(result: ShotResult) -> String {
+    match result {
+        ShotResult::Miss => "miss".to_string(),
+        ShotResult::Hit(ship_id) => format!("hit {}", ship_id),
+        ShotResult::Sunk(ship_id) => format!("sunk {}", ship_id),
+    }
+}
+
+// Read a move written by format_move, line_num places errors in the file
+pub fn parse_move(line: &str, line_num: usize) -> Result<TurnReport, BattleshipError> {
+    let bad = |field: &str, expected: &str| {
+        let column = line.find(field).map(|index| line[..index].chars().count() + 1);
+        BattleshipError::parse(line_num, column, format!("Error: Expected {} but found \"{}\".", expected, field))
+    };
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 4 {
+        return Err(BattleshipError::parse(line_num, None, format!("Error: Move \"{}\" is incomplete.", line.trim())));
+    }
+
+    let turn = fields[0].strip_suffix('.').and_then(|turn| turn.parse::<usize>().ok())
+        .ok_or_else(|| bad(fields[0], "a turn number like 12."))?;
+    let (shooter, target) = fields[1].split_once(['x', 'X'])
+        .and_then(|(shooter, target)| Some((shooter.parse::<usize>().ok()?, target.parse::<usize>().ok()?)))
+        .filter(|&(shooter, target)| shooter >= 1 && target >= 1)
+        .ok_or_else(|| bad(fields[1], "shooter x target like 1x2"))?;
+    let coord: Coord = fields[2].parse().map_err(|_| bad(fields[2], "a coordinate like A1"))?;
+
+    let ship_id = || fields.get(4).and_then(|ship_id| ship_id.parse::<usize>().ok());
+    let (result, rest) = match fields[3].to_lowercase().as_str() {
+        "miss" => (ShotResult::Miss, 4),
+        "hit" => (ShotResult::Hit(ship_id().ok_or_else(|| bad(fields.get(4).unwrap_or(&""), "the ship id hit"))?), 5),
+        "sunk" => (ShotResult::Sunk(ship_id().ok_or_else(|| bad(fields.get(4).unwrap_or(&""), "the ship id sunk"))?), 5),
+        _ => return Err(bad(fields[3], "miss, hit or sunk")),
+    };
+    let eliminated = match fields.get(rest) {
+        None => false,
+        Some(&"out") if fields.len() == rest + 1 => true,
+        Some(field) => return Err(bad(field, "out or the end of the move")),
+    };
+    Ok(TurnReport { turn, shooter: shooter - 1, target: target - 1, coord, result, eliminated })
+}
+
+// Read the tags and moves of a transcript
+pub fn parse_transcript(text: &str) -> Result<Transcript, BattleshipError> {
+    let mut transcript = Transcript::default();
+    for (index, line) in text.lines().enumerate() {
+        let line_num = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with(';') {
+            continue;
+        }
+        if let Some(tag) = trimmed.strip_prefix('[') {
+            if !transcript.moves.is_empty() {
+                return Err(BattleshipError::parse(line_num, Some(1), "Error: Tags must come before the first move.".to_string()));
+            }
+            let (key, value) = tag.strip_suffix(']')
+                .and_then(|tag| tag.split_once(' '))
+                .and_then(|(key, value)| Some((key, value.trim().strip_prefix('"')?.strip_suffix('"')?)))
+                .ok_or_else(|| BattleshipError::parse(line_num, Some(1), format!("Error: Expected a tag like [Board \"fleet.txt\"] but found {}.", trimmed)))?;
+            if transcript.get_tag(key).is_some() {
+                return Err(BattleshipError::parse(line_num, Some(2), format!("Error: Duplicate tag {}.", key)));
+            }
+            transcript.tags.push((key.to_string(), value.to_string()));
+            continue;
+        }
+        transcript.moves.push(parse_move(line, line_num)?);
+    }
+    Ok(transcript)
+}
+
+pub fn load_transcript(filename: &str) -> Result<Transcript, BattleshipError> {
+    if filename.is_empty() {
+        return Err(BattleshipError::EmptyFilename);
+    }
+    let mut text = String::new();
+    File::open(filename)
+        .and_then(|mut file| file.read_to_string(&mut text))
+        .map_err(|err| BattleshipError::io("Error opening file", err))?;
+    parse_transcript(&text)
+}
+
+// Start a transcript file for the game in myboard, replacing any file already there
+pub fn start_transcript(filename: &str, myboard: &GameData, board: &str) -> Result<(), BattleshipError> {
+    if filename.is_empty() {
+        return Err(BattleshipError::EmptyFilename);
+    }
+    let myfile = File::create(filename).map_err(|err| BattleshipError::io("Error: Failed to open specified file", err))?;
+    let write_error = |err| BattleshipError::io("Error: Failed to write file", err);
+    let mut writer = BufWriter::new(myfile);
+    write!(writer, "{}", Transcript::new(myboard, board).header()).map_err(write_error)?;
+    writer.flush().map_err(write_error)?;
+    Ok(())
+}
+
+// Add one move to the end of a transcript file
+pub fn append_move(filename: &str, report: &TurnReport) -> Result<(), BattleshipError> {
+    let mut myfile = OpenOptions::new().append(true).open(filename)
+        .map_err(|err| BattleshipError::io("Error: Failed to open specified file", err))?;
+    writeln!(myfile, "{}", format_move(report)).map_err(|err| BattleshipError::io("Error: Failed to write file", err))
+}
+
+// A transcript laid over its starting boards.  position is how many moves have been played on game
+pub struct Replay {
+    transcript: Transcript,
+    start: Vec<PlayBoard>,
+    game: GameData,
+    position: usize,
+}
+
+impl Replay {
+    // Every move is played once here, so a transcript that does not fit the boards fails before stepping starts
+    pub fn new(game: GameData, transcript: Transcript) -> Result<Self, BattleshipError> {
+        if let Some(players) = transcript.get_tag("Players") {
+            if players != game.get_boards_len().to_string() {
+                return Err(BattleshipError::Schema(format!(
+                    "Error: The transcript is for {} players but the board file has {}.", players, game.get_boards_len(),
+                )));
+            }
+        }
+        let start = game.get_boards().to_vec();
+        let mut replay = Self { transcript, start, game, position: 0 };
+        replay.goto(replay.len())?;
+        replay.goto(0)?;
+        Ok(replay)
+    }
+
+    pub fn get_game(&self) -> &GameData {
+        &self.game
+    }
+
+    pub fn get_transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+
+    pub fn len(&self) -> usize {
+        self.transcript.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transcript.moves.is_empty()
+    }
+
+    // The move that brought the game to the current position
+    pub fn last_move(&self) -> Option<&TurnReport> {
+        self.position.checked_sub(1).and_then(|index| self.transcript.moves.get(index))
+    }
+
+    // Show the game after position moves.  Going back starts again from the first move
+    pub fn goto(&mut self, position: usize) -> Result<(), BattleshipError> {
+        if position > self.len() {
+            return Err(BattleshipError::Schema(format!("Error: Move {} is past the end, the game has {} moves.", position, self.len())));
+        }
+        if position < self.position {
+            self.rewind();
+        }
+        while self.position < position {
+            let report = self.transcript.moves[self.position].clone();
+            play_move(&mut self.game, &report)?;
+            self.position += 1;
+        }
+        Ok(())
+    }
+
+    // False at the end of the game
+    pub fn forward(&mut self) -> Result<bool, BattleshipError> {
+        if self.position >= self.len() {
+            return Ok(false);
+        }
+        self.goto(self.position + 1)?;
+        Ok(true)
+    }
+
+    // False at the start of the game
+    pub fn back(&mut self) -> Result<bool, BattleshipError> {
+        if self.position == 0 {
+            return Ok(false);
+        }
+        self.goto(self.position - 1)?;
+        Ok(true)
+    }
+
+    fn rewind(&mut self) {
+        for (index, board) in self.start.iter().enumerate() {
+            if let Some(player) = self.game.boards_get_player_mut(index) {
+                *player = board.clone();
+            }
+        }
+        self.game.set_turn(0);
+        self.game.set_current_player(0);
+        self.game.set_eliminated(Vec::new());
+        self.position = 0;
+    }
+}
+
+// Fire one recorded move under the same rules as the shot that wrote it and check the game agrees with what
+// was written down.  Moves by a named shooter need not follow the turn order, see GameData::fire_out_of_turn
+fn play_move(game: &mut GameData, report: &TurnReport) -> Result<(), BattleshipError> {
+    let fail = |message: String| BattleshipError::Schema(format!("Error: Move \"{}\" {}.", format_move(report), message));
+    if report.turn != game.get_turn() + 1 {
+        return Err(fail(format!("is out of order, expected move {}", game.get_turn() + 1)));
+    }
+    let players = game.get_boards_len();
+    if report.shooter >= players || report.target >= players {
+        return Err(fail("names a player who is not in the game".to_string()));
+    }
+    let (result, eliminated) = game.fire_out_of_turn(Some(report.shooter), report.target, report.coord).map_err(|err| match err {
+        BattleshipError::GameOver => fail("comes after the game was over".to_string()),
+        BattleshipError::OwnBoard => fail("targets the shooter's own board".to_string()),
+        BattleshipError::PlayerOut(_) => fail("is by a player who is out of the game".to_string()),
+        BattleshipError::InvalidTarget(_) => fail("targets a player who is out of the game".to_string()),
+        BattleshipError::OutOfBounds(_) => fail("is off the board".to_string()),
+        err => fail(format!("failed, {}", err)),
+    })?;
+    if result != report.result {
+        return Err(fail(format!("was really {}", format_result(result))));
+    }
+    if eliminated != report.eliminated {
+        return Err(fail(if eliminated { "put the target out".to_string() } else { "left the target ships afloat".to_string() }));
+    }
+    Ok(())
+}
+
+// Load a transcript and the board file its Board tag names, ready to step through
+pub fn load_replay(filename: &str) -> Result<Replay, BattleshipError> {
+    let transcript = load_transcript(filename)?;
+    let board = transcript.get_tag("Board")
+        .ok_or_else(|| BattleshipError::Schema("Error: The transcript has no Board tag naming its board file.".to_string()))?;
+    let format = match transcript.get_tag("Format") {
+        Some(name) => Some(FileFormat::parse(name)
+            .ok_or_else(|| BattleshipError::Schema(format!("Error: Unknown board format {} in the transcript.", name)))?),
+        None => None,
+    };
+    let beside = Path::new(filename).parent().map(|dir| dir.join(board));
+    let board_path = match beside {
+        Some(path) if !Path::new(board).exists() && path.exists() => path.to_string_lossy().to_string(),
+        _ => board.to_string(),
+    };
+    let mut game = GameData::default();
+    if let Some(seed) = transcript.get_tag("Seed").and_then(|seed| seed.parse::<u64>().ok()) {
+        game.set_seed(seed);
+    }
+    file::load_file_format(&board_path, &mut game, format)?;
+    Replay::new(game, transcript)
+}
+
@@ -0,0 +1,560 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use crate::code::board::GameData;
+use crate::code::coord::Coord;
+use crate::code::enums;
+use crate::code::enums::{Direction, FileFormat, HitMiss};
+use crate::code::error::BattleshipError;
+use crate::code::file;
+use crate::code::json;
+use crate::code::utils;
+
+// Header lines at the top of a legacy board file: rows, columns, player count
+const HEADER_NAMES: [&str; 3] = ["Rows", "Columns", "Player count"];
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Severity {
+    Error,                                          // The file breaks the format or the rules, verify fails
+    Warning,                                        // Loads and plays, but is probably not what was meant
+}
+
+// One problem found in a board file.  Line and column are 1 based, line 0 is the file as a whole
+#[derive(PartialEq, Clone, Debug)]
+pub struct Violation {
+    pub line: usize,
+    pub column: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = if self.severity == Severity::Error { "Error" } else { "Warning" };
+        match (self.line, self.column) {
+            (0, _) => write!(f, "{}: {}", label, self.message),
+            (line, None) => write!(f, "{} line {}: {}", label, line, self.message),
+            (line, Some(column)) => write!(f, "{} line {}, column {}: {}", label, line, column, self.message),
+        }
+    }
+}
+
+// Everything verify found, in file order
+#[derive(Default, Clone, Debug)]
+pub struct VerifyReport {
+    pub players: usize,                             // Player blocks found in the file
+    pub violations: Vec<Violation>,
+}
+
+impl VerifyReport {
+    pub fn errors(&self) -> usize {
+        self.violations.iter().filter(|v| v.severity == Severity::Error).count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.violations.iter().filter(|v| v.severity == Severity::Warning).count()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors() == 0
+    }
+
+    fn error(&mut self, line: usize, column: Option<usize>, message: String) {
+        self.violations.push(Violation { line, column, severity: Severity::Error, message });
+    }
+
+    fn warning(&mut self, line: usize, column: Option<usize>, message: String) {
+        self.violations.push(Violation { line, column, severity: Severity::Warning, message });
+    }
+}
+
+// Check a board file against its format and fleet, a legacy file is held to the fleet in myboard if one was given.
+// Only failing to read the file is an error, every problem in it is collected in the report
+// JSON is checked as JSON when format says so or the file starts with {
+pub fn verify_file(filename: &str, myboard: &GameData, format: Option<FileFormat>) -> Result<VerifyReport, BattleshipError> {
+    if filename.is_empty() {
+        return Err(BattleshipError::EmptyFilename);
+    }
+    let file = File::open(filename).map_err(|err| BattleshipError::io("Error opening file", err))?;
+    let mut lines = Vec::new();
+    for (line_num, line) in BufReader::new(file).lines().enumerate() {
+        lines.push(line.map_err(|err| BattleshipError::io(&format!("Error reading line {}", line_num + 1), err))?);
+    }
+    let text = lines.join("\n");
+    let found = if json::is_json(&text) {
+        FileFormat::Json
+    } else if lines.first().is_some_and(|line| file::is_v2_magic(line)) {
+        FileFormat::V2
+    } else {
+        FileFormat::Legacy
+    };
+    match format {
+        Some(FileFormat::Json) => Ok(verify_json(&text, myboard)),
+        Some(format) if format != found => {
+            let mut report = VerifyReport::default();
+            report.error(1, Some(1), format!("Expected a {} file but found {}", format.get_name(), found.get_name()));
+            Ok(report)
+        }
+        _ if found == FileFormat::Json => Ok(verify_json(&text, myboard)),
+        _ => Ok(verify_lines(&lines, myboard)),
+    }
+}
+
+// What the header of a file says, in either format
+struct Header {
+    rows: Option<usize>,
+    cols: Option<usize>,
+    players: Option<usize>,
+    count_line: usize,                              // 1 based line of the player count
+    fleet: (usize, usize),                          // Smallest and largest ship id
+    first_player: usize,                            // Index of the first player's name line
+}
+
+// Same checks on a file already split into lines, in either format
+pub fn verify_lines(lines: &[String], myboard: &GameData) -> VerifyReport {
+    let mut report = VerifyReport::default();
+
+    let header = match lines.first() {
+        Some(line) if file::is_v2_magic(line) => verify_v2_header(lines, myboard, &mut report),
+        _ => verify_legacy_header(lines, myboard, &mut report),
+    };
+    let (rows, cols) = match (header.rows, header.cols) {
+        (Some(rows), Some(cols)) => (rows, cols),
+        _ => return report,                         // Without the board size the players cannot be split up
+    };
+    let declared = header.players;
+
+    let lenient = myboard.get_lenient();
+    let mut index = header.first_player;
+    while index < lines.len() {
+        if lines[index..].iter().all(|line| line.trim().is_empty()) {
+            break;                                  // Blank lines at the end are fine
+        }
+        if declared == Some(report.players) {
+            let message = format!("Trailing data after the {} declared players", report.players);
+            if !lenient {
+                report.error(index + 1, Some(1), message);
+                break;
+            }
+            report.warning(index + 1, Some(1), message);
+        }
+        let name = lines[index].trim();
+        if name.is_empty() || name.contains(',') {
+            report.error(index + 1, Some(1), format!("Expected the name of player {}", report.players + 1));
+            index += 1;
+            continue;
+        }
+        match verify_player(lines, index, (rows, cols), header.fleet, lenient, &mut report) {
+            Some(next) => {
+                index = next;
+                report.players += 1;
+            }
+            None => break,                          // Lenient and the file ends inside the last player
+        }
+    }
+
+    if let Some(declared) = declared {
+        if report.players < declared {
+            let message = format!("Header declares {} players but the file has {}", declared, report.players);
+            if lenient {
+                report.warning(header.count_line, Some(1), message);
+            } else {
+                report.error(header.count_line, Some(1), message);
+            }
+        }
+    }
+    report.violations.sort_by_key(|violation| violation.line);     // Stable, so each line keeps the order found
+    report
+}
+
+// Rows, columns and player count as three bare numbers
+fn verify_legacy_header(lines: &[String], myboard: &GameData, report: &mut VerifyReport) -> Header {
+    let mut header = [None; 3];
+    for (index, name) in HEADER_NAMES.iter().enumerate() {
+        match lines.get(index) {
+            None => report.error(index + 1, None, format!("Missing header line for {}", name.to_lowercase())),
+            Some(line) => match utils::parse_to_usize(line.trim()) {
+                Ok(num) => header[index] = Some(num),
+                Err(_) => report.error(index + 1, Some(1), format!("{} must be a number from 1 to {}, found '{}'", name, enums::MAX_SIZE, line.trim())),
+            },
+        }
+    }
+    Header {
+        rows: header[0],
+        cols: header[1],
+        players: header[2],
+        count_line: HEADER_NAMES.len(),
+        fleet: legacy_fleet(lines, myboard),
+        first_player: HEADER_NAMES.len(),
+    }
+}
+
+// A legacy file does not name its fleet, so unless --ships or --maxships gave one it runs from the
+// smallest ship id in the file to the largest
+fn legacy_fleet(lines: &[String], myboard: &GameData) -> (usize, usize) {
+    if myboard.get_fleet_given() {
+        return myboard.get_shipsizes();
+    }
+    let ship_ids: Vec<usize> = lines.iter()
+        .skip(HEADER_NAMES.len())
+        .filter(|line| line.contains(',') || line.trim().parse::<usize>().is_ok())     // Rows, as verify_player reads them
+        .flat_map(|line| line.split(',').filter_map(|field| field.trim().parse::<usize>().ok()))
+        .filter(|&ship_id| ship_id > 0)
+        .collect();
+    match (ship_ids.iter().min(), ship_ids.iter().max()) {
+        (Some(&small), Some(&large)) => (small.max(2), large.max(2)),                  // A ship 1 is still reported
+        _ => myboard.get_shipsizes(),
+    }
+}
+
+// Magic and version line then key=value headers up to the end marker, see file::load_v2_header
+fn verify_v2_header(lines: &[String], myboard: &GameData, report: &mut VerifyReport) -> Header {
+    let mut header = Header {
+        rows: None,
+        cols: None,
+        players: None,
+        count_line: 1,
+        fleet: myboard.get_shipsizes(),
+        first_player: lines.len(),
+    };
+    let version = lines[0].split_whitespace().nth(1).unwrap_or("");
+    if version.trim_start_matches(['v', 'V']).parse::<usize>() != Ok(file::V2_VERSION) {
+        report.error(1, Some(file::V2_MAGIC.len() + 2), format!("Unsupported board file version '{}', expected v{}", version, file::V2_VERSION));
+    }
+
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    let mut ended = false;
+    for (index, line) in lines.iter().enumerate().skip(1) {
+        let line = line.trim();
+        if line == file::V2_HEADER_END {
+            header.first_player = index + 1;
+            ended = true;
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+            None => {                               // Most likely the end marker is missing, check the rest as players
+                report.error(index + 1, Some(1), format!("Expected a key=value header or {}", file::V2_HEADER_END));
+                header.first_player = index;
+                ended = true;
+                break;
+            }
+        };
+        if let Some(first) = seen.insert(key.clone(), index + 1) {
+            report.error(index + 1, Some(1), format!("Header {} was already given on line {}", key, first));
+            continue;
+        }
+        let column = Some(line.find('=').unwrap_or(0) + 2);
+        let mut number = |value: &str| match utils::parse_to_usize(value) {
+            Ok(num) => Some(num),
+            Err(_) => {
+                report.error(index + 1, column, format!("Header {} must be a number from 1 to {}, found '{}'", key, enums::MAX_SIZE, value));
+                None
+            }
+        };
+        match key.as_str() {
+            "rows" => header.rows = number(value),
+            "cols" => header.cols = number(value),
+            "players" => {
+                header.players = number(value);
+                header.count_line = index + 1;
+            }
+            "ships" => {
+                let (small, large) = value.split_once('-').unwrap_or((value, value));
+                if let (Some(small), Some(large)) = (number(small.trim()), number(large.trim())) {
+                    if small < 2 || large < small {
+                        report.error(index + 1, column, format!("Ships must be a range like 2-5 with the smallest at least 2, found '{}'", value));
+                    } else {
+                        header.fleet = (small, large);
+                    }
+                }
+            }
+            "seed" if value.parse::<u64>().is_err() => report.error(index + 1, column, format!("Header seed must be a number, found '{}'", value)),
+            "rules" | "seed" | "created-by" => {}
+            _ => report.warning(index + 1, Some(1), format!("Unknown header {} is ignored", key)),
+        }
+    }
+
+    if !ended {
+        report.error(lines.len(), None, format!("The header never ends, expected a {} line", file::V2_HEADER_END));
+    }
+    for required in ["rows", "cols", "players"] {
+        if !seen.contains_key(required) {
+            report.error(1, None, format!("Missing the {} header", required));
+        }
+    }
+    header
+}
+
+// Check the player block whose name is on lines[name_index], returns the index of the line after it.
+// None when lenient and the file ends part way through the block, which the loader drops.
+// A block cut short by the end of the file gets no ship checks, they would only repeat the problem
+fn verify_player(
+    lines: &[String],
+    name_index: usize,
+    (rows, cols): (usize, usize),
+    (small, large): (usize, usize),
+    lenient: bool,
+    report: &mut VerifyReport,
+) -> Option<usize> {
+    let name = lines[name_index].trim();
+    let name_line = name_index + 1;
+    let first_row = name_index + 1;
+    let mut cells: BTreeMap<Coord, usize> = BTreeMap::new();
+
+    let mut index = first_row;
+    for row in 0..rows {
+        let line = match lines.get(index) {
+            Some(line) if line.contains(',') || line.trim().parse::<usize>().is_ok() => line,
+            None => {
+                let message = format!("The file ends inside player {}, {} of {} rows", name, row, rows);
+                if lenient {
+                    report.warning(name_line, Some(1), message);
+                    return None;
+                }
+                report.error(name_line, Some(1), message);
+                return Some(index);
+            }
+            _ => {
+                report.error(index + 1, None, format!("Player {} has {} rows, expected {}", name, row, rows));
+                break;
+            }
+        };
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != cols {
+            let column = utils::field_column(line, fields.len().min(cols));
+            report.error(index + 1, Some(column), format!("Row {} of player {} has {} columns, expected {}", row + 1, name, fields.len(), cols));
+        }
+        for (col, field) in fields.iter().enumerate().take(cols) {
+            match field.trim().parse::<usize>() {
+                Ok(0) => {}
+                Ok(ship_id) => { cells.insert(Coord { row, col }, ship_id); }
+                Err(_) => report.error(index + 1, Some(utils::field_column(line, col)), format!("Invalid cell value '{}'", field.trim())),
+            }
+        }
+        index += 1;
+    }
+
+    // Where a cell is in the file, for messages
+    let position = |coord: Coord| -> (usize, Option<usize>) {
+        let line = first_row + coord.row;
+        (line + 1, lines.get(line).map(|text| utils::field_column(text, coord.col)))
+    };
+
+    let mut ships: BTreeMap<usize, Vec<Coord>> = BTreeMap::new();
+    for (&coord, &ship_id) in cells.iter() {
+        ships.entry(ship_id).or_default().push(coord);
+    }
+    for (&ship_id, parts) in ships.iter() {
+        let (line, column) = position(parts[0]);
+        if ship_id < small || ship_id > large {
+            report.error(line, column, format!("Ship {} of player {} is not in the fleet {}..={}", ship_id, name, small, large));
+        }
+        check_ship(ship_id, parts, &cells, name, line, column, report);
+    }
+    for ship_id in small..=large {
+        if !ships.contains_key(&ship_id) {
+            report.error(name_line, Some(1), format!("Player {} is missing ship {}", name, ship_id));
+        }
+    }
+
+    for (ship_id, other, cell) in touching_ships(&cells) {
+        let (line, column) = position(cell);
+        report.warning(line, column, format!("Ships {} and {} of player {} touch at {}", ship_id, other, name, cell));
+    }
+    Some(index)
+}
+
+// Ships side by side are legal but usually a mistake, each pair is given once with the first cell found
+fn touching_ships(cells: &BTreeMap<Coord, usize>) -> Vec<(usize, usize, Coord)> {
+    let mut touching = BTreeSet::new();
+    let mut found = Vec::new();
+    for (&coord, &ship_id) in cells.iter() {
+        for next in [coord.step(Direction::Horizontal, 1), coord.step(Direction::Vertical, 1)] {
+            if let Some(&other) = cells.get(&next) {
+                if other != ship_id && touching.insert((ship_id.min(other), ship_id.max(other))) {
+                    found.push((ship_id, other, next));
+                }
+            }
+        }
+    }
+    found
+}
+
+// The same checks on a JSON game.  Positions inside the document are only known for syntax errors,
+// the rest are reported against the whole file and name the player and ship
+pub fn verify_json(text: &str, myboard: &GameData) -> VerifyReport {
+    let mut report = VerifyReport::default();
+    let game = match json::parse_json(text) {
+        Ok(game) => game,
+        Err(BattleshipError::Parse { line, column, message, .. }) => {
+            report.error(line, column, message.trim_start_matches("Error: ").to_string());
+            return report;
+        }
+        Err(err) => {
+            report.error(0, None, err.to_string().trim_start_matches("Error: ").to_string());
+            return report;
+        }
+    };
+    for (value, name) in [(game.rows, "rows"), (game.cols, "cols")] {
+        if !(enums::MIN_SIZE..=enums::MAX_SIZE).contains(&value) {
+            report.error(0, None, format!("{} must be from {} to {}, found {}", name, enums::MIN_SIZE, enums::MAX_SIZE, value));
+        }
+    }
+    let (small, large) = (game.fleet.smallest, game.fleet.largest);
+    if small < 2 || large < small {
+        report.error(0, None, format!("The fleet must run from at least 2 up, found {}..={}", small, large));
+    }
+    if !report.is_valid() {
+        return report;                              // Nothing below means anything without a board and fleet
+    }
+    if game.players.is_empty() {
+        let message = "The game has no players".to_string();
+        if myboard.get_lenient() {
+            report.warning(0, None, message);
+        } else {
+            report.error(0, None, message);
+        }
+    }
+
+    for (index, player) in game.players.iter().enumerate() {
+        let name = &player.name;
+        if name.trim().is_empty() || name.contains(',') {
+            report.error(0, None, format!("Player {} has an inappropriate or blank name", index + 1));
+        }
+        if player.number != index + 1 {
+            report.error(0, None, format!("Player {} has number {}, expected {}", name, player.number, index + 1));
+        }
+
+        let mut cells: BTreeMap<Coord, usize> = BTreeMap::new();
+        let mut ids = BTreeSet::new();
+        for ship in player.ships.iter() {
+            if ship.id < small || ship.id > large {
+                report.error(0, None, format!("Ship {} of player {} is not in the fleet {}..={}", ship.id, name, small, large));
+            }
+            if !ids.insert(ship.id) {
+                report.error(0, None, format!("Ship {} of player {} appears more than once", ship.id, name));
+                continue;
+            }
+            if ship.id == 0 {
+                continue;
+            }
+            let expected_end = ship.start.step(ship.direction, ship.id - 1);
+            if ship.end != expected_end {
+                report.error(0, None, format!("Ship {} of player {} ends at {}, expected {}", ship.id, name, ship.end, expected_end));
+            }
+            for cell in ship.start.line(ship.direction, ship.id) {
+                if cell.row >= game.rows || cell.col >= game.cols {
+                    report.error(0, None, format!("Ship {} of player {} runs off the board at {}", ship.id, name, cell));
+                    break;
+                }
+                if let Some(other) = cells.insert(cell, ship.id) {
+                    report.error(0, None, format!("Ship {} of player {} overlaps ship {} at {}", ship.id, name, other, cell));
+                }
+            }
+        }
+        for ship_id in small..=large {
+            if !ids.contains(&ship_id) {
+                report.error(0, None, format!("Player {} is missing ship {}", name, ship_id));
+            }
+        }
+        for (ship_id, other, cell) in touching_ships(&cells) {
+            report.warning(0, None, format!("Ships {} and {} of player {} touch at {}", ship_id, other, name, cell));
+        }
+
+        let mut guessed = BTreeSet::new();
+        for guess in player.guesses.iter() {
+            if guess.at.row >= game.rows || guess.at.col >= game.cols {
+                report.error(0, None, format!("Guess {} at player {} is off the board", guess.at, name));
+            } else if !guessed.insert(guess.at) {
+                report.error(0, None, format!("Guess {} at player {} is recorded twice", guess.at, name));
+            } else {
+                match (guess.result, cells.get(&guess.at)) {
+                    (HitMiss::Hit, None) => report.error(0, None, format!("Guess {} at player {} is recorded as a hit but there is no ship there", guess.at, name)),
+                    (HitMiss::Miss, Some(ship_id)) => report.error(0, None, format!("Guess {} at player {} is recorded as a miss but ship {} is there", guess.at, name, ship_id)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    report.players = game.players.len();
+    report
+}
+
+// A ship must be one straight unbroken line as long as its id
+fn check_ship(
+    ship_id: usize,
+    parts: &[Coord],
+    cells: &BTreeMap<Coord, usize>,
+    name: &str,
+    line: usize,
+    column: Option<usize>,
+    report: &mut VerifyReport,
+) {
+    let min_row = parts.iter().map(|c| c.row).min().unwrap_or(0);
+    let max_row = parts.iter().map(|c| c.row).max().unwrap_or(0);
+    let min_col = parts.iter().map(|c| c.col).min().unwrap_or(0);
+    let max_col = parts.iter().map(|c| c.col).max().unwrap_or(0);
+
+    if min_row != max_row && min_col != max_col {
+        let pieces = count_pieces(parts);
+        if pieces > 1 && parts.len() == ship_id * pieces {
+            report.error(line, column, format!("Ship {} of player {} appears {} times", ship_id, name, pieces));
+        } else {
+            report.error(line, column, format!("Ship {} of player {} is not in a straight line", ship_id, name));
+        }
+        return;
+    }
+
+    let span: Vec<Coord> = if min_row == max_row {
+        (min_col..=max_col).map(|col| Coord { row: min_row, col }).collect()
+    } else {
+        (min_row..=max_row).map(|row| Coord { row, col: min_col }).collect()
+    };
+    let gaps: Vec<Coord> = span.iter().copied().filter(|coord| !parts.contains(coord)).collect();
+    if gaps.is_empty() {
+        if parts.len() != ship_id {
+            report.error(line, column, format!("Ship {} of player {} is {} long, expected {}", ship_id, name, parts.len(), ship_id));
+        }
+        return;
+    }
+    if span.len() == ship_id && gaps.iter().all(|gap| cells.contains_key(gap)) {
+        let gap = gaps[0];
+        report.error(line, column, format!("Ship {} of player {} overlaps ship {} at {}", ship_id, name, cells[&gap], gap));
+    } else if count_pieces(parts) > 1 && parts.len() == ship_id * count_pieces(parts) {
+        report.error(line, column, format!("Ship {} of player {} appears {} times", ship_id, name, count_pieces(parts)));
+    } else {
+        report.error(line, column, format!("Ship {} of player {} has gaps at {}", ship_id, name,
+            gaps.iter().map(|gap| gap.to_string()).collect::<Vec<String>>().join(",")));
+    }
+}
+
+// Number of separate groups of side by side cells
+fn count_pieces(parts: &[Coord]) -> usize {
+    let mut unseen: BTreeSet<Coord> = parts.iter().copied().collect();
+    let mut pieces = 0;
+    while let Some(&first) = unseen.iter().next() {
+        pieces += 1;
+        let mut stack = vec![first];
+        unseen.remove(&first);
+        while let Some(coord) = stack.pop() {
+            let mut near = vec![Coord { row: coord.row + 1, col: coord.col }, Coord { row: coord.row, col: coord.col + 1 }];
+            if coord.row > 0 {
+                near.push(Coord { row: coord.row - 1, col: coord.col });
+            }
+            if coord.col > 0 {
+                near.push(Coord { row: coord.row, col: coord.col - 1 });
+            }
+            for next in near {
+                if unseen.remove(&next) {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+    pieces
+}
@@ -0,0 +1,22 @@
+// Battleship boards as a library: board modelling, file I/O, fleet placement, shot resolution and the AI.
+// The command line in main.rs is one consumer, anything else can depend on the same API.
+mod code;
+
+pub use code::{aistuff, arena, bitboard, board, coord, datacheck, enums, error, file, json, placement, save, session, strategy, transcript, utils, verify};
+
+pub use code::aistuff::AiBoard;
+pub use code::arena::{run_arena, ArenaReport, ArenaStats};
+pub use code::bitboard::BitBoard;
+pub use code::board::{create_my_board_from_player, GameData, PlayBoard, ShipBoundingBox};
+pub use code::coord::Coord;
+pub use code::enums::{Direction, FileFormat, HitMiss, Intelligence, ShotResult};
+pub use code::error::{BattleshipError, PlacementError};
+pub use code::file::{load_file, write_file, V2_VERSION};
+pub use code::json::{game_to_json, load_json, GameJson, JSON_SCHEMA_VERSION};
+pub use code::placement::PlacementMask;
+pub use code::save::{resume_game, save_game, SaveJson};
+pub use code::session::{GameResult, GameSession, TurnReport};
+pub use code::strategy::{random_fleet, CellState, ObservationBoard, RandomPlacer, Strategy};
+pub use code::transcript::{load_replay, Replay, Transcript};
+pub use code::utils::GameRng;
+pub use code::verify::{verify_file, Severity, VerifyReport, Violation};
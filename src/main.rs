@@ -1,26 +1,27 @@
-use std::fs::File;
-use std::io::{self, BufRead, stdin, BufReader};
-use std::vec;
-use crate::code::utils;
-use crate::code::commandline;
-use crate::code::interactive;
-use crate::code::board;
-mod code;
+use std::io;
+use battleship::GameData;
+use crate::cli::commandline;
+use crate::cli::interactive;
+use crate::cli::output::output_string;
+mod cli;
 
 
 fn main(){
-    utils::output_string("Welcome to the Battleship Test Program\nYou can type --help to get a list of commands");
-    let mut myboard = board::GameData::default();
+    output_string("Welcome to the Battleship Test Program\nYou can type --help to get a list of commands");
+    let mut myboard = GameData::default();
     if std::env::args().len() <= 1 {
-        utils::output_string("No command line arguments entered.");
+        output_string("No command line arguments entered.");
         myboard.set_interactive(true);
-    } else {
-        commandline::command_line_input(&mut myboard);
+    } else if !commandline::command_line_input(&mut myboard) {
+        std::process::exit(1);                                              // A verify, replay or query batch failed
     }
-    if myboard.get_interactive() == true {                                // Only enter loop if interactive set
+    if myboard.get_interactive() {                                          // Only enter loop if interactive set
         loop {
             let mut buffer = String::new();
-            io::stdin().read_line(&mut buffer).unwrap();
+            match io::stdin().read_line(&mut buffer) {
+                Ok(0) | Err(_) => break,                                    // End of input
+                Ok(_) => {}
+            }
             if interactive::eval_input(buffer, &mut myboard) {
                 break;
             }
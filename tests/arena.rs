@@ -0,0 +1,101 @@
+// Arena seating and runs between AI levels
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use battleship::arena::next_seating;
+use battleship::{random_fleet, run_arena, AiBoard, BattleshipError, Coord, GameData, GameRng, ObservationBoard, PlayBoard, Strategy};
+
+fn strategies(levels: &[usize]) -> Vec<Box<dyn Strategy>> {
+    levels.iter().map(|&level| Box::new(AiBoard::new(PlayBoard::default(), level)) as Box<dyn Strategy>).collect()
+}
+
+#[test]
+fn seating_goes_through_every_order_then_repeats() {
+    let mut seats = vec![0, 1, 2];
+    let mut seen = HashSet::new();
+    let mut targets = [[0; 3]; 3];                          // targets[a][b], a sits just before b
+    for _ in 0..6 {
+        seen.insert(seats.clone());
+        for seat in 0..3 {
+            targets[seats[seat]][seats[(seat + 1) % 3]] += 1;
+        }
+        next_seating(&mut seats);
+    }
+    assert_eq!(seen.len(), 6);
+    assert_eq!(seats, vec![0, 1, 2]);
+    for (shooter, row) in targets.iter().enumerate() {
+        for (target, &count) in row.iter().enumerate() {
+            assert_eq!(count, if shooter == target { 0 } else { 3 }, "{} before {}", shooter, target);
+        }
+    }
+}
+
+#[test]
+fn two_strategies_alternate_the_first_seat() {
+    let mut seats = vec![0, 1];
+    next_seating(&mut seats);
+    assert_eq!(seats, vec![1, 0]);
+    next_seating(&mut seats);
+    assert_eq!(seats, vec![0, 1]);
+}
+
+#[test]
+fn a_seeded_run_repeats() {
+    let myboard = small_board();
+    let first = run_arena(&myboard, &mut strategies(&[1, 3, 0]), 12).unwrap();
+    let again = run_arena(&myboard, &mut strategies(&[1, 3, 0]), 12).unwrap();
+    let wins: usize = first.stats.iter().map(|stats| stats.wins).sum();
+    assert_eq!(wins + first.unfinished, 12);
+    for (a, b) in first.stats.iter().zip(again.stats.iter()) {
+        assert_eq!((a.wins, &a.shots_to_win), (b.wins, &b.shots_to_win));
+    }
+}
+
+// Shoots like the probability AI but places its own fleet, counting the fleets it was asked for
+struct Placer {
+    ai: AiBoard,
+    placed: Rc<Cell<usize>>,
+    whole: bool,                                            // False leaves the largest ship out
+}
+
+impl Strategy for Placer {
+    fn get_name(&self) -> String {
+        "placer".to_string()
+    }
+
+    fn place_fleet(&mut self, myboard: &GameData, player: &mut PlayBoard, rng: &mut GameRng) -> Result<(), BattleshipError> {
+        self.placed.set(self.placed.get() + 1);
+        let mut short = GameData::default();
+        let (cols, rows) = myboard.get_col_row();
+        let (small, large) = myboard.get_shipsizes();
+        short.set_col_row(cols, rows);
+        short.set_shipsizes(small, Some(if self.whole { large } else { large - 1 }))?;
+        random_fleet(&short, player, rng)
+    }
+
+    fn choose_shot(&mut self, observed: &ObservationBoard, rng: &mut GameRng) -> Option<Coord> {
+        self.ai.choose_shot(observed, rng)
+    }
+}
+
+fn small_board() -> GameData {
+    let mut myboard = GameData::default();
+    myboard.set_col_row(6, 6);
+    myboard.set_shipsizes(2, Some(3)).unwrap();
+    myboard.set_seed(9);
+    myboard
+}
+
+#[test]
+fn seated_strategies_place_their_own_fleets() {
+    let placed = Rc::new(Cell::new(0));
+    let mut seated = strategies(&[1]);
+    seated.push(Box::new(Placer { ai: AiBoard::new(PlayBoard::default(), 3), placed: placed.clone(), whole: true }));
+    let report = run_arena(&small_board(), &mut seated, 5).unwrap();
+    assert_eq!(placed.get(), 5);
+    assert_eq!(report.stats[1].name, "placer");
+
+    let mut seated = strategies(&[1]);
+    seated.push(Box::new(Placer { ai: AiBoard::new(PlayBoard::default(), 3), placed, whole: false }));
+    assert!(matches!(run_arena(&small_board(), &mut seated, 5), Err(BattleshipError::IncompleteFleet(name)) if name == "placer"));
+}
@@ -0,0 +1,71 @@
+// BitBoard cell operations, including boards that span several words
+use battleship::{BitBoard, Coord};
+
+fn board_with(cols: usize, rows: usize, cells: &[(usize, usize)]) -> BitBoard {
+    let mut board = BitBoard::new(cols, rows);
+    for &(row, col) in cells {
+        assert!(board.set(Coord::new(row, col)));
+    }
+    board
+}
+
+#[test]
+fn set_test_and_clear_across_word_boundaries() {
+    let mut board = BitBoard::new(9, 9);                   // 81 cells, two words
+    let cells = [Coord::new(0, 0), Coord::new(7, 0), Coord::new(7, 1), Coord::new(8, 8)];
+    for coord in cells {
+        assert!(board.set(coord));
+    }
+    assert_eq!(board.count(), 4);
+    assert!(cells.iter().all(|&coord| board.test(coord)));
+    assert!(!board.test(Coord::new(6, 8)));
+    board.clear(Coord::new(7, 1));
+    assert!(!board.test(Coord::new(7, 1)));
+    assert_eq!(board.iter().collect::<Vec<_>>(), vec![Coord::new(0, 0), Coord::new(7, 0), Coord::new(8, 8)]);
+}
+
+#[test]
+fn cells_off_the_board_are_refused() {
+    let mut board = BitBoard::new(3, 2);
+    assert!(!board.set(Coord::new(2, 0)));
+    assert!(!board.set(Coord::new(0, 3)));
+    assert!(!board.test(Coord::new(5, 5)));
+    assert!(board.is_empty());
+}
+
+#[test]
+fn full_and_invert_stay_on_the_board() {
+    let full = BitBoard::full(10, 7);
+    assert_eq!(full.count(), 70);
+    assert!(full.invert().is_empty());
+    let one = board_with(10, 7, &[(3, 3)]);
+    assert_eq!(one.invert().count(), 69);
+    assert_eq!(one.invert().invert(), one);
+}
+
+#[test]
+fn set_operations() {
+    let a = board_with(4, 4, &[(0, 0), (1, 1), (2, 2)]);
+    let b = board_with(4, 4, &[(1, 1), (3, 3)]);
+    assert_eq!(a.union(&b).count(), 4);
+    assert_eq!(a.intersect(&b).iter().collect::<Vec<_>>(), vec![Coord::new(1, 1)]);
+    assert_eq!(a.without(&b).iter().collect::<Vec<_>>(), vec![Coord::new(0, 0), Coord::new(2, 2)]);
+}
+
+#[test]
+fn shifts_drop_cells_at_the_edge_and_never_wrap() {
+    let edges = board_with(5, 3, &[(0, 4), (1, 0), (2, 2)]);
+    assert_eq!(edges.shift_right().iter().collect::<Vec<_>>(), vec![Coord::new(1, 1), Coord::new(2, 3)]);
+    assert_eq!(edges.shift_left().iter().collect::<Vec<_>>(), vec![Coord::new(0, 3), Coord::new(2, 1)]);
+    assert_eq!(edges.shift_down().iter().collect::<Vec<_>>(), vec![Coord::new(1, 4), Coord::new(2, 0)]);
+    assert_eq!(edges.shift_up().iter().collect::<Vec<_>>(), vec![Coord::new(0, 0), Coord::new(1, 2)]);
+}
+
+#[test]
+fn neighbours_are_the_four_adjacent_cells() {
+    let centre = board_with(3, 3, &[(1, 1)]);
+    let expected = board_with(3, 3, &[(0, 1), (1, 0), (1, 2), (2, 1)]);
+    assert_eq!(centre.neighbours(), expected);
+    let corner = board_with(3, 3, &[(0, 0)]);
+    assert_eq!(corner.neighbours(), board_with(3, 3, &[(0, 1), (1, 0)]));
+}
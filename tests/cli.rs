@@ -0,0 +1,100 @@
+// Runs the command line binary against scratch board files
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use common::{scratch, TWO_PLAYERS};
+
+// Output and success of one run, from inside dir
+fn run(dir: &Path, args: &[&str]) -> (String, bool) {
+    let output = Command::new(env!("CARGO_BIN_EXE_B_ship_Rust2"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    (String::from_utf8_lossy(&output.stdout).to_string(), output.status.success())
+}
+
+#[test]
+fn guess_stops_at_the_next_command() {
+    let dir = scratch("guess-chain");
+    fs::write(dir.join("two.txt"), TWO_PLAYERS).unwrap();
+    let (out, _) = run(&dir, &["--load", "two.txt", "--guess", "2:B3", "--aishot", "2", "--seed", "4"]);
+    assert!(out.contains("Player 2 B3: HIT"), "{}", out);
+    assert!(out.contains("AI ("), "{}", out);
+    assert!(out.contains("Seed set to 4."), "{}", out);
+    assert!(!out.contains("instead of a shot list"), "{}", out);
+    assert!(!out.contains("Unrecognized command"), "{}", out);
+}
+
+#[test]
+fn guess_fires_every_shot_in_a_list() {
+    let dir = scratch("guess-list");
+    fs::write(dir.join("two.txt"), TWO_PLAYERS).unwrap();
+    let (out, _) = run(&dir, &["--load", "two.txt", "--guess", "2:B3,B4,B5,A1", "A1", "1x2:E3,E4", "2x2:A2"]);
+    for line in [
+        "Player 2 B3: HIT", "Player 2 B4: HIT", "Player 2 B5: SUNK 3", "Player 2 A1: MISS",
+        "Player 1 A1: MISS",
+        "Player 2 E3: HIT", "Player 2 E4: HIT",
+        "Error: A player cannot target their own board.",
+    ] {
+        assert!(out.contains(line), "{}: {}", line, out);
+    }
+}
+
+#[test]
+fn guess_without_shots_is_a_usage_error() {
+    let dir = scratch("guess-empty");
+    fs::write(dir.join("two.txt"), TWO_PLAYERS).unwrap();
+    let (out, _) = run(&dir, &["--load", "two.txt", "--guess", "--seed", "4"]);
+    assert!(out.contains("Usage: --guess"), "{}", out);
+    assert!(out.contains("Seed set to 4."), "{}", out);
+}
+
+#[test]
+fn queries_exit_non_zero_when_a_line_fails() {
+    let dir = scratch("queries");
+    fs::write(dir.join("two.txt"), TWO_PLAYERS).unwrap();
+    fs::write(dir.join("good.txt"), "1,B4\n2,H4\n2,Z99\n").unwrap();
+    fs::write(dir.join("bad.txt"), "1,B4\nx,B4\n").unwrap();
+
+    let (out, passed) = run(&dir, &["--load", "two.txt", "--queries", "good.txt", "--out", "good.out"]);
+    assert!(passed, "{}", out);
+    assert_eq!(fs::read_to_string(dir.join("good.out")).unwrap(), "2\n2\nOOB\n");
+
+    let (out, passed) = run(&dir, &["--load", "two.txt", "--queries", "bad.txt", "--out", "bad.out"]);
+    assert!(!passed, "{}", out);
+    assert!(out.contains("1 malformed query lines"), "{}", out);
+    assert!(fs::read_to_string(dir.join("bad.out")).unwrap().contains("ERROR line 2"));
+}
+
+#[test]
+fn convert_records_a_seed_only_when_given() {
+    let dir = scratch("convert-seed");
+    fs::write(dir.join("two.txt"), TWO_PLAYERS).unwrap();
+    run(&dir, &["--convert", "two.txt", "plain.v2"]);
+    assert!(!fs::read_to_string(dir.join("plain.v2")).unwrap().contains("seed="));
+    run(&dir, &["--seed", "5", "--convert", "two.txt", "seeded.v2"]);
+    assert!(fs::read_to_string(dir.join("seeded.v2")).unwrap().contains("seed=5\n"));
+}
+
+#[test]
+fn display_reads_the_file_as_format_says() {
+    let dir = scratch("display-format");
+    fs::write(dir.join("two.txt"), TWO_PLAYERS).unwrap();
+    let (out, _) = run(&dir, &["--format", "v2", "--display", "two.txt"]);
+    assert!(out.contains("Expected a v2 file but found legacy"), "{}", out);
+    run(&dir, &["--format", "json", "--convert", "two.txt", "two.json"]);
+    let (out, _) = run(&dir, &["--format", "json", "--display", "two.json"]);
+    assert!(out.contains("\"schema\": \"battleship\""), "{}", out);
+}
+
+#[test]
+fn verify_accepts_a_file_created_with_a_short_fleet() {
+    let dir = scratch("verify-created");
+    run(&dir, &["--seed", "3", "--create", "own.txt", "--ships", "3", "--player", "A", "--random", "--player", "B", "--random"]);
+    let (out, passed) = run(&dir, &["--verify", "own.txt"]);
+    assert!(passed, "{}", out);
+    assert!(!out.contains("missing ship"), "{}", out);
+}
@@ -0,0 +1,44 @@
+// Board files and scratch space shared by the tests, not every test file uses all of it
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use battleship::{load_file, GameData};
+
+// Ann and Bob on 8x8, ships 2 to 5 each
+pub const TWO_PLAYERS: &str = "8\n8\n2\nAnn\n\
+0,0,0,0,0,0,0,0\n0,0,0,0,0,0,0,0\n0,0,0,3,3,3,0,0\n0,2,5,5,5,5,5,0\n0,2,0,0,4,4,4,4\n0,0,0,0,0,0,0,0\n0,0,0,0,0,0,0,0\n0,0,0,0,0,0,0,0\n\
+Bob\n\
+0,0,0,0,0,0,0,0\n0,0,0,0,0,0,0,0\n0,3,0,0,5,0,0,0\n0,3,0,0,5,0,0,2\n0,3,0,0,5,0,0,2\n0,0,0,0,5,0,0,0\n0,0,0,0,5,0,0,0\n0,0,0,4,4,4,4,0\n";
+
+// Three players on 5x5 with ships 2 and 3, Cy only has the 2
+pub const THREE_PLAYERS: &str = "5\n5\n3\nAnn\n\
+2,2,0,0,0\n0,0,0,0,0\n3,3,3,0,0\n0,0,0,0,0\n0,0,0,0,0\n\
+Bob\n\
+0,0,0,0,2\n0,0,0,0,2\n0,3,0,0,0\n0,3,0,0,0\n0,3,0,0,0\n\
+Cy\n\
+0,0,0,0,0\n0,0,0,0,0\n0,0,2,0,0\n0,0,2,0,0\n0,0,0,0,0\n";
+
+// A directory of its own for each test, emptied first
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("battleship-test-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Write text to name in dir and return the path as a string
+pub fn write(dir: &Path, name: &str, text: &str) -> String {
+    let path = dir.join(name);
+    fs::write(&path, text).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+// Load a board file held in text, seeded so random choices repeat
+pub fn load(name: &str, text: &str) -> GameData {
+    let dir = scratch(name);
+    let mut game = GameData::default();
+    game.set_seed(7);
+    load_file(&write(&dir, "board.txt", text), &mut game).unwrap();
+    game
+}
@@ -0,0 +1,31 @@
+// Parsing and writing coordinates like A1 and AA10
+use battleship::{BattleshipError, Coord};
+
+#[test]
+fn parses_letters_then_a_one_based_row() {
+    assert_eq!("A1".parse::<Coord>().unwrap(), Coord::new(0, 0));
+    assert_eq!("j10".parse::<Coord>().unwrap(), Coord::new(9, 9));
+    assert_eq!(" b 7 ".parse::<Coord>().unwrap(), Coord::new(6, 1));
+    assert_eq!("Z3".parse::<Coord>().unwrap(), Coord::new(2, 25));
+    assert_eq!("AA10".parse::<Coord>().unwrap(), Coord::new(9, 26));
+    assert_eq!("BA1".parse::<Coord>().unwrap(), Coord::new(0, 52));
+}
+
+#[test]
+fn display_writes_what_parse_reads() {
+    for coord in [Coord::new(0, 0), Coord::new(9, 25), Coord::new(99, 26), Coord::new(4, 701), Coord::new(0, 702)] {
+        let text = coord.to_string();
+        assert_eq!(text.parse::<Coord>().unwrap(), coord, "{}", text);
+    }
+    assert_eq!(Coord::new(9, 26).to_string(), "AA10");
+}
+
+#[test]
+fn malformed_coordinates_say_what_is_wrong() {
+    for text in ["", "A", "12", "1A", "A1B", "A-1", "A 1 2x"] {
+        assert!(matches!(text.parse::<Coord>(), Err(BattleshipError::InvalidFormat(_))), "{:?}", text);
+    }
+    assert!(matches!("A0".parse::<Coord>(), Err(BattleshipError::OutOfBounds(_))));
+    assert!(matches!("AAAAAAAAA1".parse::<Coord>(), Err(BattleshipError::InvalidColumn(_))));
+    assert!(matches!("A99999999999999999999999".parse::<Coord>(), Err(BattleshipError::InvalidRow(_))));
+}
@@ -0,0 +1,96 @@
+// Board files written by write_file and read back by load_file, in every format
+mod common;
+
+use battleship::{load_file, write_file, Coord, FileFormat, GameData};
+use common::{load, scratch, TWO_PLAYERS};
+
+const FORMATS: [FileFormat; 3] = [FileFormat::Legacy, FileFormat::V2, FileFormat::Json];
+
+// Write game as format into dir and load it straight back
+fn write_and_load(game: &mut GameData, format: FileFormat, dir: &std::path::Path) -> GameData {
+    let path = dir.join(format!("out.{}", format.get_name())).to_string_lossy().to_string();
+    game.set_format(format);
+    game.set_filename(path.clone());
+    write_file(game).unwrap();
+    let mut loaded = GameData::default();
+    load_file(&path, &mut loaded).unwrap();
+    assert_eq!(loaded.get_format(), format);
+    loaded
+}
+
+#[test]
+fn an_unplayed_game_round_trips_in_every_format() {
+    let dir = scratch("file-fresh");
+    for format in FORMATS {
+        let mut game = load("file-fresh-source", TWO_PLAYERS);
+        let first = write_and_load(&mut game, format, &dir);
+        assert!(first == game, "{}", format.get_name());
+        let mut first = first;
+        let second = write_and_load(&mut first, format, &dir);
+        assert!(second == first, "{}", format.get_name());
+    }
+}
+
+#[test]
+fn writing_mid_game_keeps_the_ships_and_only_json_keeps_the_guesses() {
+    let dir = scratch("file-played");
+    for format in FORMATS {
+        let mut game = load("file-played-source", TWO_PLAYERS);
+        game.fire(1, Coord::new(2, 1)).unwrap();
+        game.fire(0, Coord::new(7, 7)).unwrap();
+        let before: Vec<_> = game.get_boards().to_vec();
+        let loaded = write_and_load(&mut game, format, &dir);
+
+        assert_eq!(game.get_boards(), &before[..], "write_file left {} boards alone", format.get_name());
+        assert!(loaded != game, "turn is not in a {} file", format.get_name());
+        assert_eq!(loaded.get_turn(), 0);
+        for (written, played) in loaded.get_boards().iter().zip(game.get_boards()) {
+            assert_eq!(written.return_ships().len(), played.return_ships().len());
+            for (a, b) in written.return_ships().iter().zip(played.return_ships()) {
+                assert_eq!((a.ship_id, a.start, a.end), (b.ship_id, b.start, b.end));
+            }
+            assert_eq!(written.get_hits().count() + written.get_misses().count() > 0, format == FileFormat::Json);
+        }
+        if format == FileFormat::Json {
+            assert_eq!(loaded.get_boards(), game.get_boards());
+        }
+    }
+}
+
+#[test]
+fn the_loader_holds_to_the_declared_player_count() {
+    let dir = scratch("file-count");
+    let short = common::write(&dir, "short.txt", &TWO_PLAYERS.replacen("8\n8\n2\n", "8\n8\n3\n", 1));
+    let mut game = GameData::default();
+    assert!(load_file(&short, &mut game).is_err());
+
+    let mut lenient = GameData::default();
+    lenient.set_lenient(true);
+    load_file(&short, &mut lenient).unwrap();
+    assert_eq!(lenient.get_boards_len(), 2);
+    assert_eq!(lenient.get_warnings().len(), 1);
+
+    let long = common::write(&dir, "long.txt", &TWO_PLAYERS.replacen("8\n8\n2\n", "8\n8\n1\n", 1));
+    let mut game = GameData::default();
+    assert!(load_file(&long, &mut game).is_err());
+}
+
+#[test]
+fn only_a_given_seed_is_written() {
+    let dir = scratch("file-seed");
+    let source = common::write(&dir, "two.txt", TWO_PLAYERS);
+    for (format, marker) in [(FileFormat::V2, "seed="), (FileFormat::Json, "\"seed\"")] {
+        let mut unseeded = GameData::default();
+        load_file(&source, &mut unseeded).unwrap();
+        assert!(!unseeded.get_seeded());
+        let loaded = write_and_load(&mut unseeded, format, &dir);
+        let text = std::fs::read_to_string(loaded.get_filename()).unwrap();
+        assert!(!text.contains(marker), "{}", text);
+        assert!(!loaded.get_seeded());
+
+        let mut seeded = load("file-seed-source", TWO_PLAYERS);
+        let loaded = write_and_load(&mut seeded, format, &dir);
+        assert!(loaded.get_seeded());
+        assert_eq!(loaded.get_seed(), 7);
+    }
+}
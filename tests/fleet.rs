@@ -0,0 +1,32 @@
+// Random fleets, and fleets that cannot be placed
+use std::time::{Duration, Instant};
+use battleship::{random_fleet, utils, BattleshipError, GameData, PlayBoard};
+
+// A random fleet of small..=large for one player on a cols x rows board
+fn place(cols: usize, rows: usize, small: usize, large: usize) -> Result<PlayBoard, BattleshipError> {
+    let mut game = GameData::default();
+    game.set_col_row(cols, rows);
+    game.set_shipsizes(small, Some(large))?;
+    let mut player = PlayBoard::default();
+    random_fleet(&game, &mut player, &mut utils::rng_from_seed(1))?;
+    Ok(player)
+}
+
+#[test]
+fn fleets_that_fit_are_placed_whole() {
+    for (cols, rows, small, large) in [(10, 10, 2, 5), (20, 7, 6, 13), (13, 4, 3, 10), (4, 13, 3, 10)] {
+        let player = place(cols, rows, small, large).unwrap();
+        assert_eq!(player.return_ships().len(), large - small + 1);
+    }
+}
+
+#[test]
+fn impossible_fleets_fail_quickly() {
+    // Too many ships that only fit along the long side, then ones the search has to give up on
+    for (cols, rows, small, large) in [(20, 7, 12, 19), (30, 11, 19, 30), (7, 20, 12, 19), (8, 4, 4, 8)] {
+        let start = Instant::now();
+        let result = place(cols, rows, small, large);
+        assert!(matches!(result, Err(BattleshipError::NoArrangement { .. })), "{}x{} {}..={}", cols, rows, small, large);
+        assert!(start.elapsed() < Duration::from_secs(5), "{}x{} {}..={} took {:?}", cols, rows, small, large, start.elapsed());
+    }
+}
@@ -0,0 +1,19 @@
+// Size limits the library exposes and the number parser that enforces them
+use battleship::enums::{MAX_SIZE, MIN_SIZE};
+use battleship::utils::parse_to_usize;
+use battleship::BattleshipError;
+
+#[test]
+fn size_limits_are_a_sensible_range() {
+    assert_eq!(MIN_SIZE, 1);
+    assert_eq!(MAX_SIZE, i16::MAX as usize);
+}
+
+#[test]
+fn parse_to_usize_keeps_to_the_limits() {
+    assert!(matches!(parse_to_usize("0"), Err(BattleshipError::TooSmall)));
+    assert_eq!(parse_to_usize(&MIN_SIZE.to_string()).unwrap(), MIN_SIZE);
+    assert_eq!(parse_to_usize(&MAX_SIZE.to_string()).unwrap(), MAX_SIZE);
+    assert!(matches!(parse_to_usize(&(MAX_SIZE + 1).to_string()), Err(BattleshipError::TooBig)));
+    assert!(matches!(parse_to_usize("-1"), Err(BattleshipError::NotANumber(_))));
+}
@@ -0,0 +1,85 @@
+// PlacementMask runs checked against a plain scan of the board as ships are taken out and put back
+use battleship::utils::rng_from_seed;
+use battleship::{Coord, Direction, PlacementMask};
+use rand::Rng;
+
+const DIRECTIONS: [Direction; 2] = [Direction::Horizontal, Direction::Vertical];
+
+// Every placement found by trying each cell, the way handle_random used to
+fn scan(mask: &PlacementMask, cols: usize, rows: usize, ship_size: usize) -> Vec<(Coord, Direction)> {
+    let mut found = Vec::new();
+    for direction in DIRECTIONS {
+        for row in 0..rows {
+            for col in 0..cols {
+                let start = Coord::new(row, col);
+                let end = start.step(direction, ship_size - 1);
+                if end.row < rows && end.col < cols && start.line(direction, ship_size).all(|point| mask.is_free(point)) {
+                    found.push((start, direction));
+                }
+            }
+        }
+    }
+    found
+}
+
+fn assert_matches_scan(mask: &PlacementMask, cols: usize, rows: usize) {
+    for ship_size in 1..=5 {
+        let mut expected = scan(mask, cols, rows, ship_size);
+        let mut placements = mask.placements(ship_size);
+        let key = |&(start, direction): &(Coord, Direction)| (direction == Direction::Vertical, start);
+        expected.sort_by_key(key);
+        placements.sort_by_key(key);
+        assert_eq!(placements, expected, "size {}", ship_size);
+        assert_eq!(mask.count(ship_size), expected.len());
+        for &(start, direction) in expected.iter() {
+            assert!(mask.fits(start, ship_size, direction));
+        }
+    }
+}
+
+#[test]
+fn an_empty_board_has_every_placement() {
+    let mask = PlacementMask::new(10, 10);
+    assert_eq!(mask.count(5), 2 * 10 * 6);
+    assert_eq!(mask.count(0), 0);
+    assert_matches_scan(&mask, 10, 10);
+}
+
+#[test]
+fn occupy_splits_runs_and_release_merges_them() {
+    let mut mask = PlacementMask::new(8, 6);
+    mask.occupy(Coord::new(2, 3), 3, Direction::Horizontal);    // D3 to F3, splits row 3 in two
+    assert!(!mask.fits(Coord::new(2, 0), 4, Direction::Horizontal));
+    assert!(mask.fits(Coord::new(2, 0), 3, Direction::Horizontal));
+    assert!(!mask.fits(Coord::new(0, 4), 3, Direction::Vertical));
+    assert_matches_scan(&mask, 8, 6);
+
+    mask.occupy(Coord::new(3, 0), 2, Direction::Vertical);
+    assert_matches_scan(&mask, 8, 6);
+    mask.release(Coord::new(2, 3), 3, Direction::Horizontal);    // Merges back into one run
+    assert!(mask.fits(Coord::new(2, 0), 8, Direction::Horizontal));
+    assert_matches_scan(&mask, 8, 6);
+    mask.release(Coord::new(3, 0), 2, Direction::Vertical);
+    assert!(mask.get_occupied().is_empty());
+    assert_eq!(mask.placements(3), PlacementMask::new(8, 6).placements(3));
+}
+
+#[test]
+fn random_placing_and_removing_agrees_with_a_scan() {
+    let (cols, rows) = (9, 7);
+    let mut rng = rng_from_seed(12);
+    let mut mask = PlacementMask::new(cols, rows);
+    let mut placed: Vec<(Coord, usize, Direction)> = Vec::new();
+    for _ in 0..200 {
+        let ship_size = rng.random_range(1..=5);
+        if !placed.is_empty() && rng.random_range(0..3) == 0 {
+            let (start, size, direction) = placed.swap_remove(rng.random_range(0..placed.len()));
+            mask.release(start, size, direction);
+        } else if let Some((start, direction)) = mask.sample(ship_size, &mut rng) {
+            assert!(mask.fits(start, ship_size, direction));
+            mask.occupy(start, ship_size, direction);
+            placed.push((start, ship_size, direction));
+        }
+        assert_matches_scan(&mask, cols, rows);
+    }
+}
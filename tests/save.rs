@@ -0,0 +1,136 @@
+// Saving and resuming a game, through the library and the command line
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use rand::Rng;
+use battleship::{resume_game, save_game, Coord, GameData, SaveJson};
+use common::{load, scratch, write, THREE_PLAYERS, TWO_PLAYERS};
+
+fn run(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_B_ship_Rust2")).args(args).current_dir(dir).output().unwrap();
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+// The AI shot lines of a run, which carry the seed and the cell chosen
+fn ai_lines(out: &str) -> Vec<String> {
+    out.lines().filter(|line| line.contains("AI (")).map(|line| line.trim_start_matches(":> ").to_string()).collect()
+}
+
+fn read_save(path: &Path) -> SaveJson {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn resumed_game_equals_the_saved_one() {
+    let dir = scratch("save-equal");
+    let mut game = load("save-equal-board", THREE_PLAYERS);
+    game.fire(2, Coord::new(2, 2)).unwrap();
+    game.fire(0, Coord::new(4, 4)).unwrap();
+    game.set_ai_heat_map(vec![vec![1; 5]; 5]);
+    let path = dir.join("game.json").to_string_lossy().to_string();
+    save_game(&game, &path).unwrap();
+
+    let mut resumed = GameData::default();
+    resume_game(&path, &mut resumed).unwrap();
+    assert!(resumed == game);
+    assert_eq!((resumed.get_turn(), resumed.get_current_player()), (2, 2));
+    assert_eq!(resumed.get_ai_level(), game.get_ai_level());
+    assert_eq!(resumed.get_ai_heat_map(), game.get_ai_heat_map());
+    for _ in 0..8 {
+        assert_eq!(resumed.get_rng().random::<u64>(), game.get_rng().random::<u64>());
+    }
+}
+
+#[test]
+fn saving_leaves_the_generator_alone() {
+    let dir = scratch("save-rng");
+    let mut saved = load("save-rng-a", TWO_PLAYERS);
+    let mut unsaved = load("save-rng-b", TWO_PLAYERS);
+    saved.get_rng().random::<u64>();
+    unsaved.get_rng().random::<u64>();
+    save_game(&saved, &dir.join("game.json").to_string_lossy()).unwrap();
+    for _ in 0..8 {
+        assert_eq!(saved.get_rng().random::<u64>(), unsaved.get_rng().random::<u64>());
+    }
+}
+
+#[test]
+fn ai_shots_are_the_same_with_and_without_a_save() {
+    let dir = scratch("save-cli");
+    write(&dir, "two.txt", TWO_PLAYERS);
+    let plain = ai_lines(&run(&dir, &["--seed", "4", "--load", "two.txt", "--aishot", "2", "--aishot", "1", "--aishot", "2", "--aishot", "1"]));
+    let saving = ai_lines(&run(&dir, &["--seed", "4", "--load", "two.txt", "--aishot", "2", "--aishot", "1", "--save", "mid.json", "--aishot", "2", "--aishot", "1"]));
+    assert_eq!(plain.len(), 4);
+    assert_eq!(saving, plain);
+
+    let save = read_save(&dir.join("mid.json"));
+    assert_eq!(save.ai.level, "probability");
+    assert_eq!(save.ai.heat_map.len(), 8);
+    let resumed = ai_lines(&run(&dir, &["--resume", "mid.json", "--aishot", "2", "--aishot", "1"]));
+    assert_eq!(resumed, plain[2..]);
+}
+
+#[test]
+fn refused_shots_do_not_use_up_the_turn() {
+    let dir = scratch("save-refused");
+    write(&dir, "three.txt", THREE_PLAYERS);
+    let out = run(&dir, &["--load", "three.txt", "--guess", "1x1:A1", "--save", "own.json"]);
+    assert!(out.contains("Error: A player cannot target their own board."), "{}", out);
+    let save = read_save(&dir.join("own.json"));
+    assert_eq!((save.turn, save.current), (0, 1));
+
+    // Ann sinks Cy on the fourth shot, then Bob cannot target Cy and Cy cannot shoot
+    let out = run(&dir, &["--load", "three.txt", "--guess", "1x3:C3", "2x1:E5", "3x2:E5", "1x3:C4", "2x3:A1", "3x1:B1", "--save", "out.json"]);
+    assert!(out.contains("SUNK 2 ALL-SUNK"), "{}", out);
+    assert!(out.contains("Error: Player 3 is not a valid target."), "{}", out);
+    assert!(out.contains("Error: Player 3 is out of the game and cannot shoot."), "{}", out);
+    let save = read_save(&dir.join("out.json"));
+    assert_eq!((save.turn, save.current, save.eliminated), (4, 1, vec![3]));
+}
+
+#[test]
+fn empty_fleets_are_out_when_a_game_resumes_before_the_first_shot() {
+    let dir = scratch("save-empty");
+    let fleetless = THREE_PLAYERS.replacen("Ann\n2,2,0,0,0\n0,0,0,0,0\n3,3,3,0,0\n", "Ann\n0,0,0,0,0\n0,0,0,0,0\n0,0,0,0,0\n", 1);
+    let game = load("save-empty-board", &fleetless);
+    let path = dir.join("game.json").to_string_lossy().to_string();
+    save_game(&game, &path).unwrap();
+    assert!(read_save(Path::new(&path)).eliminated.is_empty());
+
+    let mut resumed = GameData::default();
+    resume_game(&path, &mut resumed).unwrap();
+    assert_eq!(resumed.get_eliminated(), &[0]);
+    assert_eq!(resumed.get_current_player(), 1);
+    let report = resumed.fire(2, Coord::new(0, 0)).unwrap();
+    assert_eq!((report.turn, report.shooter), (1, 1));
+}
+
+#[test]
+fn bad_saves_are_refused() {
+    let dir = scratch("save-bad");
+    let game = load("save-bad-board", THREE_PLAYERS);
+    let path = dir.join("game.json");
+    save_game(&game, &path.to_string_lossy()).unwrap();
+    let good = read_save(&path);
+
+    let mut bad_key = good.clone();
+    bad_key.rng.key = "xyz".to_string();
+    let mut bad_map = good.clone();
+    bad_map.ai.heat_map = vec![vec![0; 4]; 5];
+    let mut bad_sunk = good.clone();
+    bad_sunk.sunk[2] = vec![2];
+    let mut bad_current = good.clone();
+    bad_current.current = 4;
+    for (save, message) in [
+        (bad_key, "is not 64 hex digits"),
+        (bad_map, "heat map is not 5 rows of 5 columns"),
+        (bad_sunk, "sunk ships in the save do not match"),
+        (bad_current, "Player 4 cannot have the turn"),
+    ] {
+        fs::write(&path, serde_json::to_string(&save).unwrap()).unwrap();
+        let err = resume_game(&path.to_string_lossy(), &mut GameData::default()).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
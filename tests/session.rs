@@ -0,0 +1,60 @@
+// Turn rules, which GameSession and the command line share through GameData::fire
+mod common;
+
+use battleship::{AiBoard, BattleshipError, Coord, GameSession, PlayBoard, ShotResult};
+use common::{load, THREE_PLAYERS, TWO_PLAYERS};
+
+#[test]
+fn strategies_play_a_game_to_the_end() {
+    let mut session = GameSession::new(load("session-full", TWO_PLAYERS)).unwrap();
+    let mut strategies = [AiBoard::new(PlayBoard::default(), 3), AiBoard::new(PlayBoard::default(), 1)];
+    while !session.is_over() {
+        let current = session.get_current_player();
+        session.play_turn(&mut strategies[current]).unwrap();
+    }
+    let result = session.result().unwrap();
+    assert_eq!(result.standings.len(), 2);
+    assert_eq!(result.turns, session.get_turn());
+    assert!(session.is_alive(result.winner));
+    assert!(!session.is_alive(result.standings[1]));
+}
+
+#[test]
+fn own_board_is_refused_and_keeps_the_turn() {
+    let mut game = load("session-own", TWO_PLAYERS);
+    assert!(matches!(game.fire(0, Coord::new(0, 0)), Err(BattleshipError::OwnBoard)));
+    assert_eq!(game.get_turn(), 0);
+    assert_eq!(game.get_current_player(), 0);
+    let report = game.fire(1, Coord::new(2, 1)).unwrap();
+    assert_eq!(report.result, ShotResult::Hit(3));
+    assert_eq!((report.turn, report.shooter, report.target), (1, 0, 1));
+    assert_eq!(game.get_current_player(), 1);
+}
+
+#[test]
+fn guessed_cells_do_not_use_up_the_turn() {
+    let mut game = load("session-guessed", THREE_PLAYERS);
+    game.fire(1, Coord::new(0, 0)).unwrap();
+    game.fire(2, Coord::new(0, 0)).unwrap();
+    game.fire(0, Coord::new(4, 4)).unwrap();
+    assert!(matches!(game.fire(1, Coord::new(0, 0)), Err(BattleshipError::AlreadyGuessed(_))));
+    assert_eq!((game.get_turn(), game.get_current_player()), (3, 0));
+}
+
+#[test]
+fn eliminated_players_are_skipped_and_cannot_be_targeted() {
+    let mut session = GameSession::new(load("session-out", THREE_PLAYERS)).unwrap();
+    session.fire(2, Coord::new(2, 2)).unwrap();                     // Ann hits Cy's only ship
+    session.fire(0, Coord::new(4, 4)).unwrap();                     // Bob misses Ann
+    session.fire(0, Coord::new(4, 4)).unwrap_err();                 // Ann's E5 was already guessed, Cy keeps the turn
+    let report = session.fire(1, Coord::new(4, 4)).unwrap();        // Cy misses Bob
+    assert_eq!(report.shooter, 2);
+    let sunk = session.fire(2, Coord::new(3, 2)).unwrap();          // Ann sinks Cy
+    assert_eq!(sunk.result, ShotResult::Sunk(2));
+    assert!(sunk.eliminated);
+    assert_eq!(session.get_game().get_eliminated(), &[2]);
+    assert_eq!(session.get_current_player(), 1);
+    assert!(matches!(session.fire(2, Coord::new(0, 0)), Err(BattleshipError::InvalidTarget(2))));
+    assert_eq!(session.get_turn(), 4);
+    assert_eq!(session.targets(), vec![0]);
+}
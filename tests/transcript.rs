@@ -0,0 +1,71 @@
+// Transcripts written by the command line and stepped through with Replay
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use battleship::{load_replay, ShotResult};
+use common::{scratch, write, THREE_PLAYERS};
+
+// Ann hits Cy, Bob and Cy miss, Ann sinks Cy and puts them out
+const MOVES: &str = "1. 1x3 C3 hit 2\n2. 2x1 E5 miss\n3. 3x2 E5 miss\n4. 1x3 C4 sunk 2 out\n";
+
+// Play the game in MOVES from the command line, recording it to game.pgn in dir.  The own-board 1x1 and
+// the shot with no shooter are refused
+fn record(dir: &Path) -> String {
+    write(dir, "three.txt", THREE_PLAYERS);
+    Command::new(env!("CARGO_BIN_EXE_B_ship_Rust2"))
+        .args(["--load", "three.txt", "--transcript", "game.pgn", "--guess", "1x1:A1", "1x3:C3", "2x1:E5", "3x2:E5", "1x3:C4", "3:A1"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    fs::read_to_string(dir.join("game.pgn")).unwrap()
+}
+
+#[test]
+fn command_line_games_are_recorded_move_by_move() {
+    let dir = scratch("transcript-record");
+    let text = record(&dir);
+    assert!(text.starts_with("[Event \"Battleship\"]\n[Board \"three.txt\"]\n[Format \"legacy\"]\n[Players \"3\"]\n\n"), "{}", text);
+    assert!(text.ends_with(MOVES), "{}", text);
+}
+
+#[test]
+fn replay_steps_forward_and_back() {
+    let dir = scratch("transcript-replay");
+    record(&dir);
+    let mut replay = load_replay(&dir.join("game.pgn").to_string_lossy()).unwrap();
+    assert_eq!((replay.len(), replay.get_position()), (4, 0));
+    assert!(!replay.back().unwrap());
+
+    while replay.forward().unwrap() {}
+    let last = replay.last_move().unwrap();
+    assert_eq!((last.shooter, last.target, last.result, last.eliminated), (0, 2, ShotResult::Sunk(2), true));
+    assert_eq!(replay.get_game().get_eliminated(), &[2]);
+    assert_eq!(replay.get_game().get_current_player(), 0);             // Named shooters do not pass the turn
+
+    assert!(replay.back().unwrap());
+    assert!(replay.get_game().get_eliminated().is_empty());
+    replay.goto(1).unwrap();
+    assert_eq!(replay.get_game().get_turn(), 1);
+    assert!(replay.goto(5).is_err());
+}
+
+#[test]
+fn illegal_moves_are_refused_on_replay() {
+    let dir = scratch("transcript-illegal");
+    let text = record(&dir);
+    let header = &text[..text.len() - MOVES.len()];
+    for (moves, message) in [
+        ("1. 1x1 A1 hit 2\n", "targets the shooter's own board"),
+        ("1. 1x4 A1 miss\n", "names a player who is not in the game"),
+        ("1. 1x3 C3 miss\n", "was really hit 2"),
+        ("1. 1x3 C3 hit 2\n3. 2x1 E5 miss\n", "is out of order, expected move 2"),
+        (&format!("{}5. 2x3 A1 miss\n", MOVES), "targets a player who is out of the game"),
+        (&format!("{}5. 3x2 A1 miss\n", MOVES), "is by a player who is out of the game"),
+    ] {
+        let path = write(&dir, "bad.pgn", &format!("{}{}", header, moves));
+        let err = load_replay(&path).err().unwrap();
+        assert!(err.to_string().contains(message), "{}: {}", moves, err);
+    }
+}
@@ -0,0 +1,125 @@
+// Verify mode: every violation in a board file, placed at its line and column
+mod common;
+
+use battleship::verify::verify_lines;
+use battleship::{verify_file, GameData, Severity, VerifyReport};
+use common::{scratch, write, TWO_PLAYERS};
+
+// One player on 7x7 with no ships touching
+const SPREAD: &str = "7\n7\n1\nSol\n\
+2,2,0,3,3,3,0\n0,0,0,0,0,0,0\n4,0,0,0,0,0,0\n4,0,5,5,5,5,5\n4,0,0,0,0,0,0\n4,0,0,0,0,0,0\n0,0,0,0,0,0,0\n";
+
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(|line| line.to_string()).collect()
+}
+
+fn verify(text: &str) -> VerifyReport {
+    verify_lines(&lines(text), &GameData::default())
+}
+
+// The violations as line, column and message, in report order
+fn found(report: &VerifyReport) -> Vec<(usize, Option<usize>, String)> {
+    report.violations.iter().map(|v| (v.line, v.column, v.message.clone())).collect()
+}
+
+#[test]
+fn a_good_file_has_no_violations() {
+    let report = verify(SPREAD);
+    assert!(report.is_valid());
+    assert_eq!(report.players, 1);
+    assert!(report.violations.is_empty(), "{:?}", found(&report));
+}
+
+#[test]
+fn every_violation_is_reported_in_line_order() {
+    let broken = TWO_PLAYERS
+        .replacen("0,0,0,3,3,3,0,0", "0,0,x,3,3,3,0,0", 1)          // Line 7, third cell
+        .replacen("0,3,0,0,5,0,0,2\n0,3,0,0,5,0,0,2", "0,3,0,0,5,0,0,2\n0,3,0,0,5,0,0,0", 1);  // Bob's 2 is cut short
+    let report = verify(&broken);
+    assert!(!report.is_valid());
+    let errors: Vec<_> = report.violations.iter().filter(|v| v.severity == Severity::Error)
+        .map(|v| (v.line, v.column, v.message.clone())).collect();
+    assert_eq!(errors, vec![
+        (7, Some(5), "Invalid cell value 'x'".to_string()),
+        (17, Some(15), "Ship 2 of player Bob is 1 long, expected 2".to_string()),
+    ]);
+    assert!(report.violations.windows(2).all(|pair| pair[0].line <= pair[1].line));
+}
+
+#[test]
+fn touching_ships_are_only_a_warning() {
+    let report = verify(TWO_PLAYERS);
+    assert!(report.is_valid());
+    assert_eq!(report.players, 2);
+    assert_eq!(report.warnings(), 4);
+    assert_eq!(found(&report)[0], (8, Some(7), "Ships 3 and 5 of player Ann touch at D4".to_string()));
+}
+
+#[test]
+fn violations_display_with_their_position() {
+    let dir = scratch("verify-file");
+    let path = write(&dir, "bad.txt", &TWO_PLAYERS.replacen("Ann", "Ann,Lee", 1));
+    let report = verify_file(&path, &GameData::default(), None).unwrap();
+    let shown: Vec<String> = report.violations.iter().map(|v| v.to_string()).collect();
+    assert!(shown.contains(&"Error line 4, column 1: Expected the name of player 1".to_string()), "{:?}", shown);
+}
+
+#[test]
+fn fewer_players_than_declared_is_an_error_on_the_count_line() {
+    let report = verify(&SPREAD.replacen("7\n7\n1\n", "7\n7\n2\n", 1));
+    assert!(!report.is_valid());
+    assert_eq!(found(&report), vec![(3, Some(1), "Header declares 2 players but the file has 1".to_string())]);
+}
+
+#[test]
+fn more_players_than_declared_is_trailing_data() {
+    let report = verify(&TWO_PLAYERS.replacen("8\n8\n2\n", "8\n8\n1\n", 1));
+    assert!(!report.is_valid());
+    assert!(found(&report).contains(&(13, Some(1), "Trailing data after the 1 declared players".to_string())));
+}
+
+#[test]
+fn a_truncated_player_names_its_block() {
+    let cut: String = SPREAD.lines().take(8).map(|line| format!("{}\n", line)).collect();
+    let report = verify(&cut);
+    assert!(!report.is_valid());
+    assert!(report.violations.iter().any(|v| v.message.contains("Sol")), "{:?}", found(&report));
+}
+
+#[test]
+fn lenient_turns_count_problems_into_warnings() {
+    let mut myboard = GameData::default();
+    myboard.set_lenient(true);
+    let report = verify_lines(&lines(&SPREAD.replacen("7\n7\n1\n", "7\n7\n3\n", 1)), &myboard);
+    assert!(report.is_valid());
+    assert_eq!(report.warnings(), 1);
+    let report = verify_lines(&lines(&TWO_PLAYERS.replacen("8\n8\n2\n", "8\n8\n1\n", 1)), &myboard);
+    assert!(report.is_valid());
+    assert_eq!(report.players, 2);
+}
+
+// Two players on 5x5 with only ships 3 and 4, as --create --ships 3 --maxships 4 writes
+const SHORT_FLEET: &str = "5\n5\n2\nAnn\n\
+3,3,3,0,0\n0,0,0,0,0\n4,4,4,4,0\n0,0,0,0,0\n0,0,0,0,0\n\
+Bob\n\
+0,0,0,0,3\n0,0,0,0,3\n0,0,0,0,3\n0,0,0,0,0\n4,4,4,4,0\n";
+
+#[test]
+fn a_legacy_fleet_is_read_from_the_file() {
+    let report = verify(SHORT_FLEET);
+    assert!(report.violations.is_empty(), "{:?}", found(&report));
+
+    let report = verify(&SHORT_FLEET.replacen("4,4,4,4,0\n0,0,0,0,0\n0,0,0,0,0\n", "0,0,0,0,0\n0,0,0,0,0\n0,0,0,0,0\n", 1));
+    assert_eq!(found(&report), vec![(4, Some(1), "Player Ann is missing ship 4".to_string())]);
+}
+
+#[test]
+fn a_given_fleet_is_held_to() {
+    let mut myboard = GameData::default();
+    myboard.set_shipsizes(2, Some(4)).unwrap();
+    let report = verify_lines(&lines(SHORT_FLEET), &myboard);
+    assert_eq!(found(&report), vec![
+        (4, Some(1), "Player Ann is missing ship 2".to_string()),
+        (10, Some(1), "Player Bob is missing ship 2".to_string()),
+    ]);
+}